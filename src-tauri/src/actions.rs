@@ -1,77 +1,93 @@
 use crate::audio_feedback::{play_feedback_sound, SoundType};
 use crate::ghostwriter;
-use crate::managers::audio::{AudioRecordingManager, AudioSegmentEvent};
-use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::ghostwriter::template::{self, TemplateContext};
+use crate::language::{is_other_language, language_name, DetectedLanguage};
+use crate::long_form::LongFormSession;
+use crate::managers::audio::{AudioRecordingManager, AudioSegmentEvent, RecordedAudio};
+use crate::managers::history::{HistoryManager, Profile};
+use crate::managers::inference_queue::JobPriority;
+use crate::managers::profile::ProfileManager;
+use crate::managers::transcription::{
+    low_confidence_words, TranscriptSegment, TranscriptionManager,
+};
 use crate::overlay::show_recording_overlay;
-use crate::settings::{get_openrouter_api_key, get_settings, OutputMode};
+use crate::settings::{get_settings, write_settings, AppSettings, OutputMode};
+use crate::stabilizer::TextStabilizer;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use once_cell::sync::Lazy;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tauri::{AppHandle, Emitter, Listener, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Loads the active profile, skipping the "None" profile (ID 1).
+async fn get_active_profile(app: &AppHandle, active_profile_id: Option<i64>) -> Option<Profile> {
+    let profile_id = match active_profile_id {
+        Some(1) => {
+            debug!("'None' profile selected - skipping profile instructions");
+            return None;
+        }
+        Some(id) => id,
+        None => {
+            debug!("No active profile selected");
+            return None;
+        }
+    };
 
-/// Strips punctuation from a word for fuzzy comparison.
-/// Keeps alphanumeric chars and apostrophes (for contractions like "don't").
-fn normalize_word(word: &str) -> String {
-    word.chars()
-        .filter(|c| c.is_alphanumeric() || *c == '\'')
-        .collect::<String>()
-        .to_lowercase()
-}
-
-/// Merges two overlapping text segments by detecting shared suffix/prefix.
-/// Requires at least 2 matching words to trigger dedup (avoids false positives
-/// on common single words like "the", "a", "and").
-/// Uses punctuation-stripped comparison so "all," matches "all".
-fn merge_overlapping_text(committed: &str, new_text: &str) -> String {
-    if committed.is_empty() {
-        return new_text.to_string();
-    }
-    if new_text.is_empty() {
-        return committed.to_string();
-    }
-
-    let committed_words: Vec<&str> = committed.split_whitespace().collect();
-    let new_words: Vec<&str> = new_text.split_whitespace().collect();
-
-    let max_overlap = committed_words.len().min(new_words.len());
-    let mut best_overlap = 0;
-
-    // Find longest suffix of committed that matches prefix of new
-    // Uses normalize_word() to strip punctuation (e.g. "all," == "all")
-    // Require at least 2 words to avoid false positives on common words
-    for overlap_len in (2..=max_overlap).rev() {
-        let committed_suffix = &committed_words[committed_words.len() - overlap_len..];
-        let new_prefix = &new_words[..overlap_len];
-
-        let matches = committed_suffix
-            .iter()
-            .zip(new_prefix.iter())
-            .all(|(a, b)| normalize_word(a) == normalize_word(b));
-
-        if matches {
-            best_overlap = overlap_len;
-            break; // Longest match found (iterating from largest to smallest)
+    let pm = app.state::<Arc<ProfileManager>>();
+    match pm.get_profile(profile_id).await {
+        Ok(Some(profile)) => {
+            debug!("Using profile '{}'", profile.name);
+            Some(profile)
+        }
+        Ok(None) => {
+            debug!("Active profile ID {} not found", profile_id);
+            None
+        }
+        Err(e) => {
+            error!("Failed to get profile: {}", e);
+            None
         }
     }
+}
 
-    if best_overlap > 0 {
-        let remaining = &new_words[best_overlap..];
-        if remaining.is_empty() {
-            committed.to_string()
-        } else {
-            format!("{} {}", committed, remaining.join(" "))
+/// Few-shot examples stored on the active profile
+async fn get_profile_examples(
+    app: &AppHandle,
+    profile: Option<&Profile>,
+) -> Vec<ghostwriter::FewShotExample> {
+    let Some(profile) = profile else {
+        return Vec::new();
+    };
+
+    let pm = app.state::<Arc<ProfileManager>>();
+    match pm.get_examples(profile.id).await {
+        Ok(examples) => examples
+            .into_iter()
+            .map(|e| ghostwriter::FewShotExample {
+                input: e.input,
+                output: e.output,
+            })
+            .collect(),
+        Err(e) => {
+            error!(
+                "Failed to load examples for profile '{}': {}",
+                profile.name, e
+            );
+            Vec::new()
         }
-    } else {
-        format!("{} {}", committed, new_text)
     }
 }
 
+/// Used when neither the settings nor the active profile give any instructions
+const DEFAULT_INSTRUCTIONS: &str =
+    "Improve grammar, clarity, and professionalism while maintaining the original meaning.";
+
 // Shortcut Action Trait
 pub trait ShortcutAction: Send + Sync {
     fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
@@ -80,51 +96,53 @@ pub trait ShortcutAction: Send + Sync {
 
 #[derive(Clone)]
 struct StreamingState {
-    /// Latest cumulative transcription text
-    latest_text: Arc<Mutex<String>>,
-    /// Text from audio before the current streaming window (context for initial_prompt)
-    committed_text: Arc<Mutex<String>>,
+    /// Splits window results into committed and tentative text
+    stabilizer: Arc<Mutex<TextStabilizer>>,
     /// Generation counter - increments with each transcription request
     generation: Arc<AtomicUsize>,
-    /// Whether a transcription is currently running
-    is_transcribing: Arc<AtomicBool>,
     is_recording: Arc<Mutex<bool>>,
     /// Accumulated audio buffer (never emptied during recording)
     audio_buffer: Arc<Mutex<Vec<f32>>>,
     /// Audio length at last transcription trigger
     last_transcribed_len: Arc<AtomicUsize>,
+    /// Language of the latest applied window, as identified by the engine
+    language: Arc<Mutex<Option<DetectedLanguage>>>,
 }
 
 impl StreamingState {
     fn new() -> Self {
         Self {
-            latest_text: Arc::new(Mutex::new(String::new())),
-            committed_text: Arc::new(Mutex::new(String::new())),
+            stabilizer: Arc::new(Mutex::new(TextStabilizer::new())),
             generation: Arc::new(AtomicUsize::new(0)),
-            is_transcribing: Arc::new(AtomicBool::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             audio_buffer: Arc::new(Mutex::new(Vec::new())),
             last_transcribed_len: Arc::new(AtomicUsize::new(0)),
+            language: Arc::new(Mutex::new(None)),
         }
     }
 
     fn start_recording(&self) {
         *self.is_recording.lock().unwrap() = true;
-        *self.latest_text.lock().unwrap() = String::new();
-        *self.committed_text.lock().unwrap() = String::new();
+        self.stabilizer.lock().unwrap().reset();
         self.generation.store(0, Ordering::Release);
-        self.is_transcribing.store(false, Ordering::Release);
         self.audio_buffer.lock().unwrap().clear();
         self.last_transcribed_len.store(0, Ordering::Release);
+        *self.language.lock().unwrap() = None;
     }
 
     fn stop_recording(&self) {
         *self.is_recording.lock().unwrap() = false;
     }
 
-    /// Get latest cumulative text for fallback when final transcription is empty
+    /// A queued window is stale once a newer one was queued or the recording ended;
+    /// the worker skips it rather than spend time on text nobody will see
+    fn is_stale(&self, gen: usize) -> bool {
+        !*self.is_recording.lock().unwrap() || self.generation.load(Ordering::Acquire) > gen + 1
+    }
+
+    /// Committed and tentative streaming text, used as the result of the recording
     fn get_latest_text(&self) -> String {
-        self.latest_text.lock().unwrap().clone()
+        self.stabilizer.lock().unwrap().text().full_text()
     }
 
     /// Clone a window of accumulated audio for transcription (up to max_seconds).
@@ -153,10 +171,8 @@ impl StreamingState {
 
             // Adjust last_transcribed_len to stay consistent with trimmed buffer
             let old_len = self.last_transcribed_len.load(Ordering::Acquire);
-            self.last_transcribed_len.store(
-                old_len.saturating_sub(trim_amount),
-                Ordering::Release,
-            );
+            self.last_transcribed_len
+                .store(old_len.saturating_sub(trim_amount), Ordering::Release);
         }
     }
 }
@@ -164,6 +180,12 @@ impl StreamingState {
 // Global streaming state
 static STREAMING_STATE: Lazy<StreamingState> = Lazy::new(StreamingState::new);
 
+// Chunked transcription of a long-form recording, fed from the segment listener
+static LONG_FORM: Lazy<Mutex<Option<LongFormSession>>> = Lazy::new(|| Mutex::new(None));
+
+// Text being replied to in reply mode, captured at recording start and consumed at stop
+static REPLY_CONTEXT: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
 // Initialize segment listener once globally
 pub fn setup_segment_listener(app: &AppHandle) {
     static LISTENER_INITIALIZED: std::sync::Once = std::sync::Once::new();
@@ -175,7 +197,7 @@ pub fn setup_segment_listener(app: &AppHandle) {
         // Cumulative streaming: every 1s of new audio, re-transcribe the full window
         // (up to 10s) for accurate, self-correcting real-time display
         const NEW_AUDIO_TRIGGER: usize = 16000; // Trigger every 1.0s of new audio
-        const MAX_WINDOW_SECONDS: f64 = 10.0;   // Max audio window for transcription
+        const MAX_WINDOW_SECONDS: f64 = 10.0; // Max audio window for transcription
 
         app.listen("audio-segment", move |event| {
             if !*STREAMING_STATE.is_recording.lock().unwrap() {
@@ -183,6 +205,11 @@ pub fn setup_segment_listener(app: &AppHandle) {
             }
 
             if let Ok(segment_event) = serde_json::from_str::<AudioSegmentEvent>(event.payload()) {
+                // Long-form recordings are transcribed chunk by chunk as they're made
+                if let Some(session) = LONG_FORM.lock().unwrap().as_mut() {
+                    session.push(&tm_clone, &segment_event.samples, segment_event.pause);
+                }
+
                 // Accumulate audio samples (buffer grows during entire recording)
                 let should_transcribe = {
                     let mut buf = STREAMING_STATE.audio_buffer.lock().unwrap();
@@ -197,88 +224,80 @@ pub fn setup_segment_listener(app: &AppHandle) {
                     return;
                 }
 
-                // Skip if a transcription is already running (backpressure)
-                if STREAMING_STATE.is_transcribing.swap(true, Ordering::AcqRel) {
-                    return;
-                }
-
                 // Mark current buffer length as "transcribed up to here"
                 let buf_len = STREAMING_STATE.audio_buffer.lock().unwrap().len();
-                STREAMING_STATE.last_transcribed_len.store(buf_len, Ordering::Release);
+                STREAMING_STATE
+                    .last_transcribed_len
+                    .store(buf_len, Ordering::Release);
 
                 // Trim buffer to prevent unbounded memory growth
                 STREAMING_STATE.trim_buffer(MAX_WINDOW_SECONDS);
 
                 // Clone a window of accumulated audio (up to MAX_WINDOW_SECONDS)
-                let (window_samples, is_windowed) = STREAMING_STATE.clone_audio_window(MAX_WINDOW_SECONDS);
+                let (window_samples, is_windowed) =
+                    STREAMING_STATE.clone_audio_window(MAX_WINDOW_SECONDS);
                 let gen = STREAMING_STATE.generation.fetch_add(1, Ordering::AcqRel);
 
                 // When windowed, use committed text as initial_prompt for context
                 let prompt = if is_windowed {
-                    let ct = STREAMING_STATE.committed_text.lock().unwrap();
-                    if ct.is_empty() { None } else { Some(ct.clone()) }
+                    let ct = STREAMING_STATE.stabilizer.lock().unwrap().committed_text();
+                    if ct.is_empty() {
+                        None
+                    } else {
+                        Some(ct)
+                    }
                 } else {
                     None
                 };
 
                 debug!(
-                    "[Streaming] Transcribing {:.1}s window (gen {}, windowed={}, prompt={}chars)...",
-                    window_samples.len() as f64 / 16000.0, gen, is_windowed,
+                    "[Streaming] Transcribing {:.1}s window (gen {}, windowed={}, prompt={}chars)",
+                    window_samples.len() as f64 / 16000.0,
+                    gen,
+                    is_windowed,
                     prompt.as_ref().map_or(0, |p| p.len())
                 );
 
-                let tm = tm_clone.clone();
                 let app_for_display = app_clone.clone();
 
-                std::thread::spawn(move || {
-                    match tm.transcribe_with_prompt(window_samples, prompt) {
-                        Ok(text) if !text.is_empty() => {
+                // Queued behind final passes and imports; superseded windows are skipped
+                tm_clone.transcribe_partial(
+                    window_samples,
+                    prompt,
+                    move || STREAMING_STATE.is_stale(gen),
+                    move |result| match result {
+                        Ok(partial) if !partial.text.is_empty() => {
                             // Only apply if this is still the latest generation
                             let current_gen = STREAMING_STATE.generation.load(Ordering::Acquire);
                             if gen + 1 >= current_gen {
-                                debug!("[Streaming] Gen {} result: '{}'", gen, text);
-
-                                // Build display text: committed prefix + window result
-                                let display_text = if is_windowed {
-                                    let mut committed = STREAMING_STATE.committed_text.lock().unwrap().clone();
-
-                                    // On first windowed transition, committed is empty but
-                                    // prev_latest has the full non-windowed text. Use it to
-                                    // avoid losing text that precedes the window.
-                                    if committed.is_empty() {
-                                        let prev_latest = STREAMING_STATE.latest_text.lock().unwrap().clone();
-                                        if !prev_latest.is_empty() {
-                                            committed = prev_latest;
-                                        }
-                                    }
-
-                                    let merged = merge_overlapping_text(&committed, &text);
-
-                                    // Update committed_text to the merged result so that the
-                                    // next generation always has the best available full text.
-                                    *STREAMING_STATE.committed_text.lock().unwrap() = merged.clone();
-
-                                    merged
-                                } else {
-                                    text.clone()
-                                };
-
-                                *STREAMING_STATE.latest_text.lock().unwrap() = display_text.clone();
-
-                                if let Some(window) = app_for_display.get_webview_window("recording_overlay") {
+                                debug!("[Streaming] Gen {} result: '{}'", gen, partial.text);
+
+                                // Words are committed once consecutive windows agree on them
+                                let streaming_text = STREAMING_STATE
+                                    .stabilizer
+                                    .lock()
+                                    .unwrap()
+                                    .push(&partial.text);
+                                *STREAMING_STATE.language.lock().unwrap() = partial.language;
+
+                                if let Some(window) =
+                                    app_for_display.get_webview_window("recording_overlay")
+                                {
                                     // Stop repositioning once streaming text appears
                                     crate::overlay::set_streaming_active(true);
-                                    let _ = window.emit("td-partial", &display_text);
+                                    let _ = window.emit("td-partial", &streaming_text);
                                 }
                             } else {
-                                debug!("[Streaming] Gen {} stale (current {}), discarding", gen, current_gen);
+                                debug!(
+                                    "[Streaming] Gen {} stale (current {}), discarding",
+                                    gen, current_gen
+                                );
                             }
                         }
                         Ok(_) => debug!("[Streaming] Gen {} was empty", gen),
                         Err(e) => debug!("[Streaming] Gen {} failed: {}", gen, e),
-                    }
-                    STREAMING_STATE.is_transcribing.store(false, Ordering::Release);
-                });
+                    },
+                );
             }
         });
 
@@ -286,65 +305,466 @@ pub fn setup_segment_listener(app: &AppHandle) {
     });
 }
 
-// Transcribe Action
-struct TranscribeAction;
+/// Starts a recording for `binding_id`: streaming transcription, model load, tray,
+/// overlay and microphone (with start feedback sound).
+fn begin_recording(app: &AppHandle, binding_id: &str) {
+    // Ensure segment listener is initialized (only happens once)
+    setup_segment_listener(app);
 
-impl ShortcutAction for TranscribeAction {
-    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
-        let start_time = Instant::now();
-        debug!("TranscribeAction::start called for binding: {}", binding_id);
+    // Initialize streaming state
+    STREAMING_STATE.start_recording();
 
-        // Ensure segment listener is initialized (only happens once)
-        setup_segment_listener(app);
+    // Load model and keep it loaded during recording for streaming
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.set_suppress_unload(true);
+    tm.cache_recording_options();
+    tm.initiate_model_load();
 
-        // Initialize streaming state
-        STREAMING_STATE.start_recording();
+    change_tray_icon(app, TrayIconState::Recording);
+    show_recording_overlay(app);
 
-        // Load model and keep it loaded during recording for streaming
-        let tm = app.state::<Arc<TranscriptionManager>>();
-        tm.set_suppress_unload(true);
-        tm.initiate_model_load();
+    // Notify overlay that streaming is starting (but don't expand yet)
+    if let Some(window) = app.get_webview_window("recording_overlay") {
+        let _ = window.emit("td-show", ());
+    }
 
-        let binding_id = binding_id.to_string();
-        change_tray_icon(app, TrayIconState::Recording);
-        show_recording_overlay(app);
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+
+    // Get the microphone mode to determine audio feedback timing
+    let settings = get_settings(app);
+    let is_always_on = settings.always_on_microphone;
+    debug!("Microphone mode - always_on: {}", is_always_on);
 
-        // Notify overlay that streaming is starting (but don't expand yet)
-        if let Some(window) = app.get_webview_window("recording_overlay") {
-            let _ = window.emit("td-show", ());
+    // Long-form recordings go to disk and are transcribed in chunks while recording
+    *LONG_FORM.lock().unwrap() = settings.long_form_dictation.then(LongFormSession::new);
+
+    if is_always_on {
+        // Always-on mode: Play audio feedback immediately
+        debug!("Always-on mode: Playing audio feedback immediately");
+        play_feedback_sound(app, SoundType::Start);
+        let recording_started = rm.try_start_recording(binding_id);
+        debug!("Recording started: {}", recording_started);
+    } else {
+        // On-demand mode: Start recording first, then play audio feedback
+        // This allows the microphone to be activated before playing the sound
+        debug!("On-demand mode: Starting recording first, then audio feedback");
+        let recording_start_time = Instant::now();
+        if rm.try_start_recording(binding_id) {
+            debug!("Recording started in {:?}", recording_start_time.elapsed());
+            // Small delay to ensure microphone stream is active
+            let app_clone = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                debug!("Playing delayed audio feedback");
+                play_feedback_sound(&app_clone, SoundType::Start);
+            });
+        } else {
+            debug!("Failed to start recording");
         }
+    }
+}
 
-        let rm = app.state::<Arc<AudioRecordingManager>>();
+/// Synchronous part of stopping a recording, run on the shortcut thread.
+fn end_recording(app: &AppHandle) {
+    // Stop accepting new segments (no flush needed - final transcription uses full audio)
+    STREAMING_STATE.stop_recording();
 
-        // Get the microphone mode to determine audio feedback timing
-        let settings = get_settings(app);
-        let is_always_on = settings.always_on_microphone;
-        debug!("Microphone mode - always_on: {}", is_always_on);
+    // Allow model unload again (after final transcription completes)
+    let tm_for_unload = app.state::<Arc<TranscriptionManager>>();
+    tm_for_unload.set_suppress_unload(false);
+    tm_for_unload.clear_recording_options();
 
-        if is_always_on {
-            // Always-on mode: Play audio feedback immediately
-            debug!("Always-on mode: Playing audio feedback immediately");
-            play_feedback_sound(app, SoundType::Start);
-            let recording_started = rm.try_start_recording(&binding_id);
-            debug!("Recording started: {}", recording_started);
-        } else {
-            // On-demand mode: Start recording first, then play audio feedback
-            // This allows the microphone to be activated before playing the sound
-            debug!("On-demand mode: Starting recording first, then audio feedback");
-            let recording_start_time = Instant::now();
-            if rm.try_start_recording(&binding_id) {
-                debug!("Recording started in {:?}", recording_start_time.elapsed());
-                // Small delay to ensure microphone stream is active
-                let app_clone = app.clone();
-                std::thread::spawn(move || {
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    debug!("Playing delayed audio feedback");
-                    play_feedback_sound(&app_clone, SoundType::Start);
-                });
-            } else {
-                debug!("Failed to start recording");
+    change_tray_icon(app, TrayIconState::Idle);
+
+    // Play audio feedback for recording stop
+    play_feedback_sound(app, SoundType::Stop);
+}
+
+/// Hides the overlay and resets the tray when there is nothing to paste.
+fn dismiss_overlay(ah: &AppHandle) {
+    if let Some(window) = ah.get_webview_window("recording_overlay") {
+        let _ = window.emit("td-hide", ());
+    }
+    utils::hide_recording_overlay(ah);
+    change_tray_icon(ah, TrayIconState::Idle);
+}
+
+/// Final text sent to the overlay, with the words to highlight as possibly misheard
+#[derive(Serialize)]
+struct FinalTextEvent<'a> {
+    text: &'a str,
+    uncertain_words: &'a [String],
+}
+
+/// A finished recording and its transcription
+struct FinishedRecording {
+    audio: RecordedAudio,
+    transcription: String,
+    duration_seconds: f64,
+    /// Segment and word timings, when the final transcription produced them
+    segments: Option<Vec<TranscriptSegment>>,
+    /// Language the transcription is in, if known
+    language: Option<DetectedLanguage>,
+}
+
+/// Collects the recorded audio and the final transcription. Dismisses the overlay and
+/// returns None when nothing was recorded or nothing was recognised.
+async fn finish_recording(ah: &AppHandle, binding_id: &str) -> Option<FinishedRecording> {
+    let rm = Arc::clone(&ah.state::<Arc<AudioRecordingManager>>());
+    let tm = Arc::clone(&ah.state::<Arc<TranscriptionManager>>());
+
+    let stop_recording_time = Instant::now();
+    let audio = match rm.stop_recording(binding_id) {
+        Some(audio) => audio,
+        None => {
+            debug!("No samples retrieved from recording stop");
+            LONG_FORM.lock().unwrap().take();
+            dismiss_overlay(ah);
+            return None;
+        }
+    };
+    debug!(
+        "Recording stopped and samples retrieved in {:?}, sample count: {}",
+        stop_recording_time.elapsed(),
+        audio.sample_count()
+    );
+
+    // Calculate recording duration from samples (16kHz sample rate)
+    const WHISPER_SAMPLE_RATE: f64 = 16000.0;
+    let duration_seconds = audio.sample_count() as f64 / WHISPER_SAMPLE_RATE;
+    debug!("Recording duration: {:.2}s", duration_seconds);
+
+    // By default the streaming result is used directly — no re-transcription needed
+    let mut transcription = STREAMING_STATE.get_latest_text();
+    let mut segments = None;
+    let mut language = STREAMING_STATE.language.lock().unwrap().take();
+    info!(
+        "[Final] Streaming text ({}chars): '{}'",
+        transcription.len(),
+        transcription
+    );
+
+    // In accurate mode, or with a separate streaming model, the selected model transcribes
+    // the full recording and its text replaces the streamed one. Otherwise this only runs
+    // as a fallback when streaming produced nothing (very short recording).
+    let settings = get_settings(ah);
+    let accurate = settings.accurate_final_pass;
+    let dual_model = TranscriptionManager::streaming_model(&settings).is_some();
+    // A long-form recording has been transcribed in chunks while it was made; only the
+    // chunks still queued are waited for. A spilled recording always goes this way.
+    let long_form = LONG_FORM
+        .lock()
+        .unwrap()
+        .take()
+        .or_else(|| matches!(audio, RecordedAudio::Spilled { .. }).then(LongFormSession::new));
+    if let Some(session) = long_form {
+        let tail = audio.read_from(session.received()).unwrap_or_else(|e| {
+            error!("Failed to read the end of the recording: {}", e);
+            Vec::new()
+        });
+        let tm = Arc::clone(&tm);
+        match tauri::async_runtime::spawn_blocking(move || session.finish(&tm, &tail)).await {
+            Ok(t) if !t.text.is_empty() => {
+                info!(
+                    "[Final] Long-form transcription ({}chars): '{}'",
+                    t.text.len(),
+                    t.text
+                );
+                transcription = t.text;
+                segments = Some(t.segments);
+                language = t.language;
             }
+            Ok(_) => debug!("Long-form transcription was empty, keeping streaming text"),
+            Err(e) => error!("Long-form transcription task failed: {}", e),
         }
+    } else if let RecordedAudio::Samples(samples) = &audio {
+        if accurate || dual_model || transcription.is_empty() {
+            debug!(
+                "Running full transcription (accurate: {}, dual model: {})",
+                accurate, dual_model
+            );
+            let samples = samples.clone();
+            let tm = Arc::clone(&tm);
+            // Waiting on the inference queue blocks, so keep it off the async runtime
+            let full = tauri::async_runtime::spawn_blocking(move || {
+                if accurate {
+                    // Chunked at silences, so long recordings stay within the model's window
+                    tm.transcribe_accurate(samples)
+                } else {
+                    tm.transcribe_timed(samples, JobPriority::Final)
+                }
+            })
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Final transcription task failed: {}", e)));
+            match full {
+                Ok(t) if !t.text.is_empty() || transcription.is_empty() => {
+                    info!(
+                        "[Final] Full transcription ({}chars): '{}'",
+                        t.text.len(),
+                        t.text
+                    );
+                    transcription = t.text;
+                    segments = Some(t.segments);
+                    language = t.language;
+                }
+                Ok(_) => debug!("Full transcription was empty, keeping streaming text"),
+                Err(err) if transcription.is_empty() => {
+                    debug!("Fallback transcription error: {}", err);
+                    dismiss_overlay(ah);
+                    return None;
+                }
+                Err(err) => error!("Full transcription failed, keeping streaming text: {}", err),
+            }
+        }
+    }
+
+    if transcription.is_empty() {
+        audio.discard();
+        dismiss_overlay(ah);
+        return None;
+    }
+
+    if let Some(language) = &language {
+        debug!(
+            "Transcription language: {} ({:?})",
+            language.code, language.probability
+        );
+    }
+
+    Some(FinishedRecording {
+        audio,
+        transcription,
+        duration_seconds,
+        segments,
+        language,
+    })
+}
+
+/// Persists segment and word timings for a history entry. The pasted text usually comes
+/// from the streaming windows, which carry no usable timings, so the full recording is
+/// transcribed once more in the background. Long-form recordings always come with timings.
+async fn save_transcript_timings(
+    ah: &AppHandle,
+    hm: &HistoryManager,
+    history_id: i64,
+    audio: RecordedAudio,
+    segments: Option<Vec<TranscriptSegment>>,
+) {
+    let segments = match (segments, audio) {
+        (Some(segments), _) => segments,
+        (None, RecordedAudio::Spilled { .. }) => return,
+        (None, RecordedAudio::Samples(samples)) => {
+            let tm = Arc::clone(&ah.state::<Arc<TranscriptionManager>>());
+            // The model may already have been unloaded after the last streaming window
+            tm.initiate_model_load();
+            let timed = tauri::async_runtime::spawn_blocking(move || {
+                tm.transcribe_timed(samples, JobPriority::Import)
+            })
+            .await;
+            match timed {
+                Ok(Ok(timed)) => timed.segments,
+                Ok(Err(e)) => {
+                    error!("Failed to compute word timings: {}", e);
+                    return;
+                }
+                Err(e) => {
+                    error!("Word timing task failed: {}", e);
+                    return;
+                }
+            }
+        }
+    };
+
+    if let Err(e) = hm.save_transcription_segments(history_id, &segments) {
+        error!("Failed to save transcription segments: {}", e);
+    }
+}
+
+/// Hides the overlay and pastes `text` into the focused app on the main thread.
+fn paste_on_main_thread(ah: &AppHandle, text: String) {
+    let ah_clone = ah.clone();
+    let paste_time = Instant::now();
+    ah.run_on_main_thread(move || {
+        // Hide the overlay BEFORE pasting to prevent it from stealing focus
+        if let Some(window) = ah_clone.get_webview_window("recording_overlay") {
+            let _ = window.emit("td-hide", ());
+        }
+        utils::hide_recording_overlay(&ah_clone);
+
+        // Small delay to ensure overlay is fully hidden before pasting
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        debug!("Pasting text on main thread: '{}'", text);
+        match utils::paste(text, ah_clone.clone()) {
+            Ok(()) => debug!("Text pasted successfully in {:?}", paste_time.elapsed()),
+            Err(e) => error!("Failed to paste transcription: {}", e),
+        }
+        change_tray_icon(&ah_clone, TrayIconState::Idle);
+    })
+    .unwrap_or_else(|e| {
+        error!("Failed to run paste on main thread: {:?}", e);
+        dismiss_overlay(ah);
+    });
+}
+
+/// Values for template variables in custom instructions. The clipboard is only read
+/// when one of the instructions actually uses `{clipboard}`.
+fn template_context(
+    app: &AppHandle,
+    settings: &AppSettings,
+    profile: Option<&Profile>,
+    profile_instructions: Option<&str>,
+    language: Option<&DetectedLanguage>,
+) -> TemplateContext {
+    let needs_clipboard = template::uses_variable(&settings.custom_instructions, "clipboard")
+        || profile_instructions.is_some_and(|i| template::uses_variable(i, "clipboard"));
+    let clipboard = if needs_clipboard {
+        app.clipboard().read_text().ok()
+    } else {
+        None
+    };
+
+    TemplateContext {
+        now: chrono::Local::now(),
+        language: language.map_or_else(|| settings.selected_language.clone(), |l| l.code.clone()),
+        profile_name: profile.map(|p| p.name.clone()),
+        clipboard,
+        user_name: settings.user_name.clone(),
+    }
+}
+
+/// Adds the tokens and cost of a ghostwriter request to the usage ledger
+fn record_ghostwriter_usage(ah: &AppHandle, rewrite: &ghostwriter::Rewrite) {
+    if let Some(usage) = &rewrite.usage {
+        let hm = ah.state::<Arc<HistoryManager>>();
+        if let Err(e) = hm.record_ghostwriter_usage(&rewrite.model, usage) {
+            error!("Failed to record ghostwriter usage: {}", e);
+        }
+    }
+}
+
+/// Checks the monthly ghostwriter budget. Once it's used up, ghostwriter and reply
+/// output are switched back to plain transcripts and the user is told why.
+fn ghostwriter_budget_exhausted(ah: &AppHandle) -> bool {
+    let mut settings = get_settings(ah);
+    let Some(budget) = settings.ghostwriter_monthly_budget else {
+        return false;
+    };
+
+    let spent = match ah
+        .state::<Arc<HistoryManager>>()
+        .get_month_ghostwriter_cost()
+    {
+        Ok(spent) => spent,
+        Err(e) => {
+            error!(
+                "Failed to read ghostwriter spend, not enforcing budget: {}",
+                e
+            );
+            return false;
+        }
+    };
+    if spent < budget {
+        return false;
+    }
+
+    warn!(
+        "Monthly ghostwriter budget reached (${:.2} of ${:.2})",
+        spent, budget
+    );
+
+    if matches!(
+        settings.output_mode,
+        OutputMode::Ghostwriter | OutputMode::Reply
+    ) {
+        settings.output_mode = OutputMode::Transcript;
+        write_settings(ah, settings);
+        let _ = ah.emit("settings-changed", ());
+    }
+
+    if let Some(main_window) = ah.get_webview_window("main") {
+        let notification_data = serde_json::json!({
+            "title": "Monthly Budget Reached",
+            "message": format!(
+                "Ghostwriting has used ${:.2} of your ${:.2} monthly budget, so output \
+                 switched to plain transcripts. Raise the budget in settings to keep \
+                 ghostwriting this month.",
+                spent, budget
+            ),
+            "type": "error"
+        });
+        let _ = main_window.emit("show-notification", notification_data);
+    }
+    true
+}
+
+/// Streams ghostwriter tokens into the overlay as they arrive
+fn overlay_partial_callback(ah: &AppHandle) -> impl Fn(&str) + Send + Sync {
+    let overlay = ah.get_webview_window("recording_overlay");
+    move |text: &str| {
+        if let Some(window) = &overlay {
+            let _ = window.emit("gw-partial", text);
+        }
+    }
+}
+
+/// Shows a ghostwriter failure in the overlay and as a persistent notification in the main window
+fn notify_ghostwriter_error(ah: &AppHandle, title: &str, message: String) {
+    // Emit error event to overlay for immediate feedback
+    if let Some(overlay_window) = ah.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("ghostwriter-error", &message);
+    }
+
+    // Emit notification event to main window for persistent alert
+    if let Some(main_window) = ah.get_webview_window("main") {
+        let notification_data = serde_json::json!({
+            "title": title,
+            "message": message,
+            "type": "error"
+        });
+        let _ = main_window.emit("show-notification", notification_data);
+    }
+}
+
+/// Tells the user a rewrite was discarded by the guardrails and the dictation pasted as is
+fn notify_rewrite_rejected(ah: &AppHandle, rejected: &ghostwriter::RewriteRejected) {
+    if let Some(overlay_window) = ah.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("ghostwriter-rejected", &rejected.reason);
+    }
+
+    if let Some(main_window) = ah.get_webview_window("main") {
+        let notification_data = serde_json::json!({
+            "title": "Rewrite Rejected",
+            "message": format!("{}; original pasted", rejected),
+            "type": "warning"
+        });
+        let _ = main_window.emit("show-notification", notification_data);
+    }
+}
+
+// Transcribe Action
+struct TranscribeAction;
+
+impl ShortcutAction for TranscribeAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        let start_time = Instant::now();
+        debug!("TranscribeAction::start called for binding: {}", binding_id);
+
+        // Reply mode: grab what we're replying to while the target app still has focus
+        // (the overlay takes focus once shown)
+        let reply_context = if get_settings(app).output_mode == OutputMode::Reply {
+            let context = utils::capture_reply_context(app);
+            debug!(
+                "Reply context captured: {} chars",
+                context.as_ref().map_or(0, |c| c.len())
+            );
+            context
+        } else {
+            None
+        };
+        *REPLY_CONTEXT.lock().unwrap() = reply_context;
+
+        begin_recording(app, binding_id);
 
         debug!(
             "TranscribeAction::start completed in {:?}",
@@ -356,255 +776,376 @@ impl ShortcutAction for TranscribeAction {
         let stop_time = Instant::now();
         debug!("TranscribeAction::stop called for binding: {}", binding_id);
 
-        // Stop accepting new segments (no flush needed - final transcription uses full audio)
-        STREAMING_STATE.stop_recording();
-
-        // Allow model unload again (after final transcription completes)
-        let tm_for_unload = app.state::<Arc<TranscriptionManager>>();
-        tm_for_unload.set_suppress_unload(false);
+        end_recording(app);
 
         let ah = app.clone();
-        let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
-        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
         let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
-
-        change_tray_icon(app, TrayIconState::Idle);
-
-        // Play audio feedback for recording stop
-        play_feedback_sound(app, SoundType::Stop);
-
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
 
         tauri::async_runtime::spawn(async move {
-            let binding_id = binding_id.clone(); // Clone for the inner async task
             debug!(
                 "Starting async transcription task for binding: {}",
                 binding_id
             );
 
-            let stop_recording_time = Instant::now();
-            if let Some(samples) = rm.stop_recording(&binding_id) {
-                debug!(
-                    "Recording stopped and samples retrieved in {:?}, sample count: {}",
-                    stop_recording_time.elapsed(),
-                    samples.len()
+            let Some(FinishedRecording {
+                audio,
+                transcription,
+                duration_seconds,
+                segments,
+                language,
+            }) = finish_recording(&ah, &binding_id).await
+            else {
+                return;
+            };
+
+            // Apply ghostwriting if enabled
+            let settings = get_settings(&ah);
+            // Only a timed final transcription carries word confidences
+            let uncertain_words = segments
+                .as_deref()
+                .map(|segments| {
+                    low_confidence_words(segments, settings.low_confidence_threshold as f32)
+                })
+                .unwrap_or_default();
+            let verify_hints: &[String] = if settings.ghostwriter_verify_hints {
+                &uncertain_words
+            } else {
+                &[]
+            };
+            debug!(
+                "Output mode: {:?}, Checking if ghostwriting should run",
+                settings.output_mode
+            );
+            let (final_text, ghostwritten_text) = if matches!(
+                settings.output_mode,
+                OutputMode::Ghostwriter | OutputMode::Reply
+            ) && !ghostwriter_budget_exhausted(&ah)
+            {
+                debug!("Ghostwriter mode enabled, processing transcription");
+
+                // Update overlay to show "Ghostwriting..." now that transcription is done
+                utils::emit_overlay_state(&ah, "ghostwriting");
+
+                // Active profile supplies extra instructions and may override the provider,
+                // model and generation parameters
+                let active_profile = get_active_profile(&ah, settings.active_profile_id).await;
+                let profile_instructions = active_profile
+                    .as_ref()
+                    .and_then(|p| p.custom_instructions.clone());
+
+                // Fill in template variables such as {date} or {user_name}
+                let template_context = template_context(
+                    &ah,
+                    &settings,
+                    active_profile.as_ref(),
+                    profile_instructions.as_deref(),
+                    language.as_ref(),
                 );
+                let global_instructions =
+                    template::render(&settings.custom_instructions, &template_context);
+                let profile_instructions = profile_instructions
+                    .map(|instructions| template::render(&instructions, &template_context));
+
+                // Combine global and profile-specific instructions
+                let combined_instructions = match (
+                    global_instructions.as_str(),
+                    profile_instructions.as_deref(),
+                ) {
+                    ("", None) => DEFAULT_INSTRUCTIONS.to_string(),
+                    ("", Some(profile_inst)) => profile_inst.to_string(),
+                    (global_inst, None) => global_inst.to_string(),
+                    (global_inst, Some(profile_inst)) => {
+                        format!(
+                            "{}\n\nAdditional context for this profile:\n{}",
+                            global_inst, profile_inst
+                        )
+                    }
+                };
 
-                // Calculate recording duration from samples (16kHz sample rate)
-                const WHISPER_SAMPLE_RATE: f64 = 16000.0;
-                let duration_seconds = samples.len() as f64 / WHISPER_SAMPLE_RATE;
-                debug!("Recording duration: {:.2}s", duration_seconds);
+                debug!("Using combined instructions: {}", combined_instructions);
+
+                let (provider, model, params) =
+                    ghostwriter::resolve_for_profile(&settings, active_profile.as_ref());
+                let examples = get_profile_examples(&ah, active_profile.as_ref()).await;
+                let retry = ghostwriter::retry_policy(&settings, active_profile.as_ref());
+                let reply_context = REPLY_CONTEXT.lock().unwrap().take();
+                let spoken_language = language.as_ref().and_then(|l| language_name(&l.code));
+
+                let input = ghostwriter::RewriteInput {
+                    original_text: &transcription,
+                    custom_instructions: &combined_instructions,
+                    examples: &examples,
+                    reply_context: reply_context.as_deref(),
+                    language: spoken_language,
+                    uncertain_words: verify_hints,
+                };
 
-                let samples_clone = samples.clone(); // Clone for history saving
+                let result = if settings.ghostwriter_streaming {
+                    // Show the rewrite in the overlay as tokens arrive
+                    let on_partial = overlay_partial_callback(&ah);
+                    ghostwriter::process_text_streaming(
+                        &input,
+                        provider.as_ref(),
+                        &model,
+                        &retry,
+                        &params,
+                        &on_partial,
+                    )
+                    .await
+                } else {
+                    ghostwriter::process_text(&input, provider.as_ref(), &model, &retry, &params)
+                        .await
+                };
 
-                // Use streaming result directly — no re-transcription needed
-                let mut transcription = STREAMING_STATE.get_latest_text();
-                info!("[Final] Using streaming text ({}chars): '{}'", transcription.len(), transcription);
+                // Reject rewrites that answered the dictation instead of rewriting it
+                let result = result.and_then(|rewrite| {
+                    // Rejected rewrites were still paid for
+                    record_ghostwriter_usage(&ah, &rewrite);
+                    if settings.ghostwriter_guardrails {
+                        // Replies and translations share few words with the dictation by design
+                        let check_overlap = settings.output_mode != OutputMode::Reply
+                            && !language
+                                .as_ref()
+                                .is_some_and(|l| is_other_language(&rewrite.text, &l.code));
+                        ghostwriter::validate_rewrite(
+                            &transcription,
+                            &rewrite.text,
+                            check_overlap,
+                        )?;
+                    }
+                    Ok(rewrite)
+                });
 
-                // Fallback: if streaming text is empty (very short recording), do one quick transcription
-                if transcription.is_empty() {
-                    debug!("Streaming text empty, falling back to full transcription");
-                    match tm.transcribe(samples) {
-                        Ok(t) => {
-                            info!("[Final] Fallback transcription ({}chars): '{}'", t.len(), t);
-                            transcription = t;
+                match result {
+                    Ok(ghostwritten) => {
+                        debug!(
+                            "Ghostwriting successful via '{}'. Original: '{}', Ghostwritten: '{}'",
+                            ghostwritten.model, transcription, ghostwritten.text
+                        );
+                        (ghostwritten.text.clone(), Some(ghostwritten))
+                    }
+                    Err(e) => {
+                        match e.downcast_ref::<ghostwriter::RewriteRejected>() {
+                            // The provider worked; its output just wasn't a usable rewrite
+                            Some(rejected) => notify_rewrite_rejected(&ah, rejected),
+                            None => {
+                                error!("Ghostwriting failed, using original transcription: {}", e);
+                                notify_ghostwriter_error(
+                                    &ah,
+                                    "Ghostwriter Failed",
+                                    format!(
+                                        "{}\n\nOriginal transcription was pasted instead.\n\n\
+                                         Please check your {} config in settings.",
+                                        e,
+                                        provider.name()
+                                    ),
+                                );
+                            }
                         }
-                        Err(err) => {
-                            debug!("Fallback transcription error: {}", err);
-                            if let Some(window) = ah.get_webview_window("recording_overlay") {
-                                let _ = window.emit("td-hide", ());
+                        (transcription.clone(), None)
+                    }
+                }
+            } else {
+                (transcription.clone(), None)
+            };
+
+            // Save to history with both original and ghostwritten text, then word timings
+            let active_profile_id = settings.active_profile_id;
+            let ah_for_history = ah.clone();
+            tauri::async_runtime::spawn(async move {
+                let saved = hm
+                    .save_transcription(
+                        &audio,
+                        transcription,
+                        ghostwritten_text,
+                        active_profile_id,
+                        Some(duration_seconds),
+                    )
+                    .await;
+                match saved {
+                    Ok(Some(history_id)) => {
+                        if let Some(language) = &language {
+                            if let Err(e) = hm.save_language(history_id, language) {
+                                error!("Failed to save transcription language: {}", e);
                             }
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                            return;
                         }
+                        save_transcript_timings(&ah_for_history, &hm, history_id, audio, segments)
+                            .await
+                    }
+                    Ok(None) => audio.discard(),
+                    Err(e) => {
+                        error!("Failed to save transcription to history: {}", e);
+                        audio.discard();
                     }
                 }
+            });
+
+            // Send final result to recording overlay
+            if let Some(window) = ah.get_webview_window("recording_overlay") {
+                let _ = window.emit(
+                    "td-final",
+                    FinalTextEvent {
+                        text: &final_text,
+                        uncertain_words: &uncertain_words,
+                    },
+                );
+            }
 
-                if !transcription.is_empty() {
-                    // Apply ghostwriting if enabled
-                    let settings = get_settings(&ah);
-                    debug!(
-                        "Output mode: {:?}, Checking if ghostwriting should run",
-                        settings.output_mode
-                    );
-                    let (final_text, ghostwritten_text) = if settings.output_mode
-                        == OutputMode::Ghostwriter
-                    {
-                        debug!("Ghostwriter mode enabled, processing transcription");
-
-                        // Update overlay to show "Ghostwriting..." now that transcription is done
-                        utils::emit_overlay_state(&ah, "ghostwriting");
-
-                        // Get active profile's custom instructions if available
-                        // Skip if "None" profile (ID 1) is selected
-                        let profile_instructions = if let Some(profile_id) =
-                            settings.active_profile_id
-                        {
-                            if profile_id == 1 {
-                                debug!("'None' profile selected - skipping profile instructions");
-                                None
-                            } else {
-                                use crate::managers::profile::ProfileManager;
-                                let pm = ah.state::<Arc<ProfileManager>>();
-                                match pm.get_profile(profile_id).await {
-                                    Ok(Some(profile)) => {
-                                        debug!(
-                                            "Using profile '{}' custom instructions",
-                                            profile.name
-                                        );
-                                        profile.custom_instructions
-                                    }
-                                    Ok(None) => {
-                                        debug!("Active profile ID {} not found", profile_id);
-                                        None
-                                    }
-                                    Err(e) => {
-                                        error!("Failed to get profile: {}", e);
-                                        None
-                                    }
-                                }
-                            }
-                        } else {
-                            debug!("No active profile selected");
-                            None
-                        };
-
-                        // Combine global and profile-specific instructions
-                        let combined_instructions = match (settings.custom_instructions.as_str(), profile_instructions.as_deref()) {
-                                    ("", None) => "Improve grammar, clarity, and professionalism while maintaining the original meaning.".to_string(),
-                                    ("", Some(profile_inst)) => profile_inst.to_string(),
-                                    (global_inst, None) => global_inst.to_string(),
-                                    (global_inst, Some(profile_inst)) => {
-                                        format!("{}\n\nAdditional context for this profile:\n{}", global_inst, profile_inst)
-                                    }
-                                };
-
-                        debug!("Using combined instructions: {}", combined_instructions);
-
-                        // Get API key from OS keychain, fall back to settings file
-                        let api_key = get_openrouter_api_key()
-                            .or_else(|| settings.openrouter_api_key.clone());
-
-                        match ghostwriter::process_text(
-                            &transcription,
-                            &api_key,
-                            &settings.openrouter_model,
-                            &combined_instructions,
-                        )
-                        .await
-                        {
-                            Ok(ghostwritten) => {
-                                debug!(
-                                    "Ghostwriting successful. Original: '{}', Ghostwritten: '{}'",
-                                    transcription, ghostwritten
-                                );
-                                (ghostwritten.clone(), Some(ghostwritten))
-                            }
-                            Err(e) => {
-                                error!("Ghostwriting failed, using original transcription: {}", e);
+            paste_on_main_thread(&ah, final_text);
+        });
 
-                                // Emit error event to overlay for immediate feedback
-                                if let Some(overlay_window) =
-                                    ah.get_webview_window("recording_overlay")
-                                {
-                                    let error_message = e.to_string();
-                                    let _ = overlay_window.emit("ghostwriter-error", error_message);
-                                }
+        debug!(
+            "TranscribeAction::stop completed in {:?}",
+            stop_time.elapsed()
+        );
+    }
+}
 
-                                // Emit notification event to main window for persistent alert
-                                if let Some(main_window) = ah.get_webview_window("main") {
-                                    let notification_data = serde_json::json!({
-                                        "title": "Ghostwriter Failed",
-                                        "message": format!("{}\n\nOriginal transcription was pasted instead.\n\nPlease check your OpenRouter config in settings.", e),
-                                        "type": "error"
-                                    });
-                                    let _ =
-                                        main_window.emit("show-notification", notification_data);
-                                }
+// Transform Selection Action
+//
+// Select text, hold the shortcut and speak an instruction ("make this more formal",
+// "translate to Spanish"); the selection is replaced with the rewritten text.
+struct TransformSelectionAction;
 
-                                (transcription.clone(), None)
-                            }
-                        }
-                    } else {
-                        (transcription.clone(), None)
-                    };
-
-                    // Save to history with both original and ghostwritten text
-                    let hm_clone = Arc::clone(&hm);
-                    let transcription_for_history = transcription.clone();
-                    let active_profile_id = settings.active_profile_id;
-                    tauri::async_runtime::spawn(async move {
-                        if let Err(e) = hm_clone
-                            .save_transcription(
-                                samples_clone,
-                                transcription_for_history,
-                                ghostwritten_text,
-                                active_profile_id,
-                                Some(duration_seconds),
-                            )
-                            .await
-                        {
-                            error!("Failed to save transcription to history: {}", e);
-                        }
-                    });
+// Selection captured when the shortcut was pressed, consumed when it is released
+static TRANSFORM_SELECTION: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
 
-                    // Send final result to recording overlay
-                    if let Some(window) = ah.get_webview_window("recording_overlay") {
-                        let _ = window.emit("td-final", &final_text);
-                    }
+impl ShortcutAction for TransformSelectionAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        let start_time = Instant::now();
+        debug!(
+            "TransformSelectionAction::start called for binding: {}",
+            binding_id
+        );
 
-                    let transcription_clone = final_text.clone();
-                    let ah_clone = ah.clone();
-                    let paste_time = Instant::now();
-                    ah.run_on_main_thread(move || {
-                        // Hide the overlay BEFORE pasting to prevent it from stealing focus
-                        if let Some(window) = ah_clone.get_webview_window("recording_overlay") {
-                            let _ = window.emit("td-hide", ());
-                        }
-                        utils::hide_recording_overlay(&ah_clone);
+        // Copy the selection while the target app still has focus
+        let selection = utils::capture_selection(app);
+        debug!(
+            "Selection captured: {} chars",
+            selection.as_ref().map_or(0, |s| s.len())
+        );
+        let has_selection = selection.is_some();
+        *TRANSFORM_SELECTION.lock().unwrap() = selection;
+
+        if !has_selection {
+            show_recording_overlay(app);
+            notify_ghostwriter_error(
+                app,
+                "Nothing Selected",
+                "Select some text first, then hold the shortcut and say how to change it."
+                    .to_string(),
+            );
+            return;
+        }
 
-                        // Small delay to ensure overlay is fully hidden before pasting
-                        std::thread::sleep(std::time::Duration::from_millis(50));
+        begin_recording(app, binding_id);
 
-                        debug!("Pasting text on main thread: '{}'", transcription_clone);
-                        match utils::paste(transcription_clone, ah_clone.clone()) {
-                            Ok(()) => {
-                                debug!("Text pasted successfully in {:?}", paste_time.elapsed())
-                            }
-                            Err(e) => error!("Failed to paste transcription: {}", e),
-                        }
-                        change_tray_icon(&ah_clone, TrayIconState::Idle);
-                    })
-                    .unwrap_or_else(|e| {
-                        error!("Failed to run paste on main thread: {:?}", e);
-                        if let Some(window) = ah.get_webview_window("recording_overlay") {
-                            let _ = window.emit("td-hide", ());
-                        }
-                        utils::hide_recording_overlay(&ah);
-                        change_tray_icon(&ah, TrayIconState::Idle);
-                    });
-                } else {
+        debug!(
+            "TransformSelectionAction::start completed in {:?}",
+            start_time.elapsed()
+        );
+    }
+
+    fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        debug!(
+            "TransformSelectionAction::stop called for binding: {}",
+            binding_id
+        );
+
+        let Some(selection) = TRANSFORM_SELECTION.lock().unwrap().take() else {
+            // Nothing was selected on press; the overlay is only showing the error
+            utils::hide_recording_overlay(app);
+            return;
+        };
+
+        end_recording(app);
+
+        let ah = app.clone();
+        let binding_id = binding_id.to_string();
+
+        tauri::async_runtime::spawn(async move {
+            let Some(FinishedRecording {
+                audio,
+                transcription: instruction,
+                ..
+            }) = finish_recording(&ah, &binding_id).await
+            else {
+                return;
+            };
+            // Instructions aren't saved to history
+            audio.discard();
+            info!("Transform instruction: '{}'", instruction);
+
+            if ghostwriter_budget_exhausted(&ah) {
+                dismiss_overlay(&ah);
+                return;
+            }
+
+            utils::emit_overlay_state(&ah, "ghostwriting");
+
+            let settings = get_settings(&ah);
+            let active_profile = get_active_profile(&ah, settings.active_profile_id).await;
+            let (provider, model, params) =
+                ghostwriter::resolve_for_profile(&settings, active_profile.as_ref());
+            let retry = ghostwriter::retry_policy(&settings, active_profile.as_ref());
+
+            // No guardrails here: a transform may legitimately change length or wording entirely
+            let result = if settings.ghostwriter_streaming {
+                let on_partial = overlay_partial_callback(&ah);
+                ghostwriter::transform_selection(
+                    &instruction,
+                    &selection,
+                    provider.as_ref(),
+                    &model,
+                    &retry,
+                    &params,
+                    Some(&on_partial),
+                )
+                .await
+            } else {
+                ghostwriter::transform_selection(
+                    &instruction,
+                    &selection,
+                    provider.as_ref(),
+                    &model,
+                    &retry,
+                    &params,
+                    None,
+                )
+                .await
+            };
+
+            match result {
+                Ok(rewrite) => {
+                    debug!(
+                        "Selection transformed via '{}': '{}'",
+                        rewrite.model, rewrite.text
+                    );
+                    record_ghostwriter_usage(&ah, &rewrite);
                     if let Some(window) = ah.get_webview_window("recording_overlay") {
-                        let _ = window.emit("td-hide", ());
+                        let _ = window.emit(
+                            "td-final",
+                            FinalTextEvent {
+                                text: &rewrite.text,
+                                uncertain_words: &[],
+                            },
+                        );
                     }
-                    utils::hide_recording_overlay(&ah);
-                    change_tray_icon(&ah, TrayIconState::Idle);
+                    paste_on_main_thread(&ah, rewrite.text);
                 }
-            } else {
-                debug!("No samples retrieved from recording stop");
-                if let Some(window) = ah.get_webview_window("recording_overlay") {
-                    let _ = window.emit("td-hide", ());
+                Err(e) => {
+                    // Leave the selection untouched rather than pasting the instruction over it
+                    error!("Transforming selection failed: {}", e);
+                    notify_ghostwriter_error(
+                        &ah,
+                        "Transform Failed",
+                        format!("{}\n\nYour selection was left unchanged.", e),
+                    );
+                    dismiss_overlay(&ah);
                 }
-                utils::hide_recording_overlay(&ah);
-                change_tray_icon(&ah, TrayIconState::Idle);
             }
         });
-
-        debug!(
-            "TranscribeAction::stop completed in {:?}",
-            stop_time.elapsed()
-        );
     }
 }
 
@@ -638,6 +1179,10 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "transform_selection".to_string(),
+        Arc::new(TransformSelectionAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
@@ -647,101 +1192,17 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
 
 #[cfg(test)]
 mod tests {
-    use super::{merge_overlapping_text, normalize_word};
-
-    #[test]
-    fn test_normalize_word() {
-        assert_eq!(normalize_word("all,"), "all");
-        assert_eq!(normalize_word("glitchy."), "glitchy");
-        assert_eq!(normalize_word("don't"), "don't");
-        assert_eq!(normalize_word("Hello!"), "hello");
-        assert_eq!(normalize_word("(test)"), "test");
-    }
-
-    #[test]
-    fn test_merge_no_overlap() {
-        let result = merge_overlapping_text("hello world", "foo bar");
-        assert_eq!(result, "hello world foo bar");
-    }
-
-    #[test]
-    fn test_merge_with_overlap() {
-        let result = merge_overlapping_text(
-            "the quick brown fox jumps",
-            "brown fox jumps over the lazy dog",
-        );
-        assert_eq!(result, "the quick brown fox jumps over the lazy dog");
-    }
-
-    #[test]
-    fn test_merge_full_overlap() {
-        let result = merge_overlapping_text("hello world foo", "hello world foo");
-        assert_eq!(result, "hello world foo");
-    }
-
-    #[test]
-    fn test_merge_empty_committed() {
-        let result = merge_overlapping_text("", "hello world");
-        assert_eq!(result, "hello world");
-    }
-
-    #[test]
-    fn test_merge_empty_new() {
-        let result = merge_overlapping_text("hello world", "");
-        assert_eq!(result, "hello world");
-    }
-
-    #[test]
-    fn test_merge_single_word_overlap_ignored() {
-        // Single common words like "the" should not trigger dedup
-        let result = merge_overlapping_text("I saw the", "the cat");
-        assert_eq!(result, "I saw the the cat");
-    }
-
-    #[test]
-    fn test_merge_case_insensitive() {
-        let result = merge_overlapping_text(
-            "Hello World Foo",
-            "hello world foo bar baz",
-        );
-        assert_eq!(result, "Hello World Foo bar baz");
-    }
-
-    #[test]
-    fn test_merge_punctuation_mismatch() {
-        // Whisper often adds/removes punctuation between windows
-        // "all," vs "all" should still match
-        let result = merge_overlapping_text(
-            "First of all, the voice seems",
-            "the voice seems to be slightly glitchy",
-        );
-        assert_eq!(
-            result,
-            "First of all, the voice seems to be slightly glitchy"
-        );
-    }
+    use super::ACTION_MAP;
+    use crate::settings::get_default_settings;
 
     #[test]
-    fn test_merge_trailing_period_mismatch() {
-        let result = merge_overlapping_text(
-            "I still notice a delay.",
-            "a delay between when I finish speaking",
-        );
-        assert_eq!(
-            result,
-            "I still notice a delay. between when I finish speaking"
-        );
-    }
-
-    #[test]
-    fn test_merge_comma_vs_no_comma() {
-        let result = merge_overlapping_text(
-            "do some testing. First of all,",
-            "First of all the voice seems slightly glitchy",
-        );
-        assert_eq!(
-            result,
-            "do some testing. First of all, the voice seems slightly glitchy"
-        );
+    fn test_default_bindings_have_actions() {
+        for id in get_default_settings().bindings.keys() {
+            assert!(
+                ACTION_MAP.contains_key(id),
+                "no action for binding '{}'",
+                id
+            );
+        }
     }
 }
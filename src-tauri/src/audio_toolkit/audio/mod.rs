@@ -1,10 +1,12 @@
 // Re-export all audio components
+mod decode;
 mod device;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
+pub use decode::{decode_audio_file, SUPPORTED_AUDIO_EXTENSIONS};
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
 pub use recorder::{AudioRecorder, RecorderError};
 pub use resampler::FrameResampler;
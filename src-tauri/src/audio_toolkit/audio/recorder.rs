@@ -1,4 +1,7 @@
 use std::{
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
     sync::{mpsc, Arc, Mutex},
     time::Duration,
 };
@@ -7,7 +10,8 @@ use cpal::{
     traits::{DeviceTrait, HostTrait, StreamTrait},
     Device, Sample, SizedSample,
 };
-use log::{error, info};
+use hound::{WavSpec, WavWriter};
+use log::{debug, error, info};
 
 use crate::audio_toolkit::{
     audio::{AudioVisualiser, FrameResampler},
@@ -17,7 +21,9 @@ use crate::audio_toolkit::{
 };
 
 enum Cmd {
-    Start,
+    /// Start recording, writing samples to the WAV file at the path instead of
+    /// keeping them in memory when one is given
+    Start(Option<PathBuf>),
     Stop(mpsc::Sender<Vec<f32>>),
     Shutdown,
 }
@@ -54,9 +60,12 @@ pub struct AudioRecorder {
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
-    segment_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    segment_cb: Option<Arc<SegmentCallback>>,
 }
 
+/// Receives speech as it's recorded, and whether the segment ended at a pause
+type SegmentCallback = dyn Fn(Vec<f32>, bool) + Send + Sync + 'static;
+
 impl AudioRecorder {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         Ok(AudioRecorder {
@@ -84,7 +93,7 @@ impl AudioRecorder {
 
     pub fn with_segment_callback<F>(mut self, cb: F) -> Self
     where
-        F: Fn(Vec<f32>) + Send + Sync + 'static,
+        F: Fn(Vec<f32>, bool) + Send + Sync + 'static,
     {
         self.segment_cb = Some(Arc::new(cb));
         self
@@ -209,7 +218,16 @@ impl AudioRecorder {
 
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = &self.cmd_tx {
-            tx.send(Cmd::Start)?;
+            tx.send(Cmd::Start(None))?;
+        }
+        Ok(())
+    }
+
+    /// Starts recording straight to a 16 kHz WAV file, for recordings too long to keep
+    /// in memory. `stop` then finalizes the file and returns no samples.
+    pub fn start_spilling(&self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Start(Some(path)))?;
         }
         Ok(())
     }
@@ -301,13 +319,62 @@ impl AudioRecorder {
     }
 }
 
+/// Recorded samples written to disk as they arrive
+struct SpillFile {
+    writer: WavWriter<BufWriter<File>>,
+    path: PathBuf,
+}
+
+impl SpillFile {
+    fn create(path: PathBuf) -> Result<Self, hound::Error> {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: constants::WHISPER_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        Ok(Self {
+            writer: WavWriter::create(&path, spec)?,
+            path,
+        })
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), hound::Error> {
+        for sample in samples {
+            self.writer
+                .write_sample((sample * i16::MAX as f32) as i16)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) {
+        match self.writer.finalize() {
+            Ok(()) => debug!("Finished spilled recording {:?}", self.path),
+            Err(e) => error!(
+                "Failed to finalize spilled recording {:?}: {}",
+                self.path, e
+            ),
+        }
+    }
+}
+
+/// Moves the samples collected so far into the spill file, if recording to one
+fn spill_samples(spill: &mut Option<SpillFile>, samples: &mut Vec<f32>) {
+    if let Some(file) = spill {
+        if let Err(e) = file.write(samples) {
+            error!("Failed to write recording to {:?}: {}", file.path, e);
+        }
+        samples.clear();
+    }
+}
+
 fn run_consumer(
     in_sample_rate: u32,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
-    segment_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    segment_cb: Option<Arc<SegmentCallback>>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -318,6 +385,8 @@ fn run_consumer(
     let mut processed_samples = Vec::<f32>::new();
     let mut segment_buffer = Vec::<f32>::new(); // Buffer for current segment
     let mut recording = false;
+    // Long recordings go to disk so `processed_samples` stays small
+    let mut spill: Option<SpillFile> = None;
 
     // Periodic streaming: emit chunks every ~1s of speech audio for real-time display
     const STREAMING_CHUNK_SAMPLES: usize = 16000; // 1 second at 16kHz
@@ -339,7 +408,7 @@ fn run_consumer(
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
         segment_buf: &mut Vec<f32>,
-        segment_cb: &Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+        segment_cb: &Option<Arc<SegmentCallback>>,
     ) -> bool {
         if !recording {
             return false;
@@ -398,7 +467,7 @@ fn run_consumer(
             // Emit segment if boundary detected and we have data
             if should_emit && !segment_buffer.is_empty() {
                 if let Some(cb) = &segment_cb {
-                    cb(std::mem::take(&mut segment_buffer));
+                    cb(std::mem::take(&mut segment_buffer), true);
                 }
             }
 
@@ -406,17 +475,26 @@ fn run_consumer(
             // This enables real-time transcription display during continuous speech
             if !should_emit && segment_buffer.len() >= STREAMING_CHUNK_SAMPLES {
                 if let Some(cb) = &segment_cb {
-                    cb(std::mem::take(&mut segment_buffer));
+                    cb(std::mem::take(&mut segment_buffer), false);
                 }
             }
         });
+        spill_samples(&mut spill, &mut processed_samples);
 
         // non-blocking check for a command
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                Cmd::Start => {
+                Cmd::Start(spill_path) => {
                     processed_samples.clear();
                     segment_buffer.clear();
+                    spill = spill_path.and_then(|path| match SpillFile::create(path.clone()) {
+                        Ok(file) => Some(file),
+                        Err(e) => {
+                            // Fall back to keeping the recording in memory
+                            error!("Failed to create spill file {:?}: {}", path, e);
+                            None
+                        }
+                    });
                     recording = true;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
@@ -441,10 +519,15 @@ fn run_consumer(
                     // Emit any remaining segment data
                     if !segment_buffer.is_empty() {
                         if let Some(cb) = &segment_cb {
-                            cb(std::mem::take(&mut segment_buffer));
+                            cb(std::mem::take(&mut segment_buffer), true);
                         }
                     }
 
+                    spill_samples(&mut spill, &mut processed_samples);
+                    if let Some(file) = spill.take() {
+                        file.finish();
+                    }
+
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
                 }
                 Cmd::Shutdown => return,
@@ -5,8 +5,9 @@ pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    decode_audio_file, list_input_devices, list_output_devices, save_wav_file, AudioRecorder,
+    CpalDeviceInfo, SUPPORTED_AUDIO_EXTENSIONS,
 };
-pub use text::apply_custom_words;
+pub use text::{apply_custom_words, build_initial_prompt, word_diff, DiffChunk};
 pub use utils::get_cpal_host;
-pub use vad::{SileroVad, VoiceActivityDetector};
+pub use vad::{split_on_silence, SileroVad, VoiceActivityDetector};
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use strsim::jaro_winkler;
 
 /// Applies custom word corrections to transcribed text using fuzzy matching
@@ -115,6 +116,143 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
+/// Whether a run of words is shared by both texts or only in one of them
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    /// Only in the new text
+    Insert,
+    /// Only in the original text
+    Delete,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DiffChunk {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// Lowercased word without surrounding punctuation, so "Hello," matches "hello"
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Word-level diff of `original` against `revised`, based on the longest common
+/// subsequence of words. Case and surrounding punctuation are ignored when matching;
+/// chunks keep the words as written (equal runs use the revised text).
+pub fn word_diff(original: &str, revised: &str) -> Vec<DiffChunk> {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = revised.split_whitespace().collect();
+    let old_norm: Vec<String> = old.iter().map(|w| normalize_word(w)).collect();
+    let new_norm: Vec<String> = new.iter().map(|w| normalize_word(w)).collect();
+
+    // lcs[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old_norm[i] == new_norm[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut chunks: Vec<DiffChunk> = Vec::new();
+    let mut push = |op: DiffOp, word: &str| match chunks.last_mut() {
+        Some(last) if last.op == op => {
+            last.text.push(' ');
+            last.text.push_str(word);
+        }
+        _ => chunks.push(DiffChunk {
+            op,
+            text: word.to_string(),
+        }),
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old_norm[i] == new_norm[j] {
+            push(DiffOp::Equal, new[j]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push(DiffOp::Delete, old[i]);
+            i += 1;
+        } else {
+            push(DiffOp::Insert, new[j]);
+            j += 1;
+        }
+    }
+    for word in &old[i..] {
+        push(DiffOp::Delete, word);
+    }
+    for word in &new[j..] {
+        push(DiffOp::Insert, word);
+    }
+
+    chunks
+}
+
+/// Whisper keeps at most 224 prompt tokens; this many characters stays safely below that
+pub const MAX_INITIAL_PROMPT_CHARS: usize = 800;
+/// Share of the prompt the vocabulary may take, so context is never crowded out
+const MAX_VOCABULARY_PROMPT_CHARS: usize = 400;
+
+/// Builds a Whisper initial prompt from custom vocabulary and preceding text
+///
+/// Terms are listed first, in order, until the vocabulary budget is used up. The
+/// context (usually text already committed in this recording) follows and is
+/// trimmed from the front at a word boundary, since Whisper conditions most on
+/// the text right before the audio.
+///
+/// # Returns
+/// None when there is neither vocabulary nor context
+pub fn build_initial_prompt(vocabulary: &[String], context: Option<&str>) -> Option<String> {
+    let mut glossary = String::new();
+    for term in vocabulary
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+    {
+        let separator = if glossary.is_empty() { "" } else { ", " };
+        if glossary.len() + separator.len() + term.len() + 1 > MAX_VOCABULARY_PROMPT_CHARS {
+            break;
+        }
+        glossary.push_str(separator);
+        glossary.push_str(term);
+    }
+    if !glossary.is_empty() {
+        glossary.push('.');
+    }
+
+    let context = context.map(str::trim).unwrap_or_default();
+    let budget = MAX_INITIAL_PROMPT_CHARS.saturating_sub(glossary.len() + 1);
+    let context = if context.len() <= budget {
+        context
+    } else {
+        let mut start = context.len() - budget;
+        while !context.is_char_boundary(start) {
+            start += 1;
+        }
+        let tail = &context[start..];
+        // Drop the partial first word
+        match tail.find(char::is_whitespace) {
+            Some(space) => tail[space..].trim_start(),
+            None => "",
+        }
+    };
+
+    match (glossary.is_empty(), context.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(glossary),
+        (true, false) => Some(context.to_string()),
+        (false, false) => Some(format!("{} {}", glossary, context)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,4 +310,61 @@ mod tests {
         let result = apply_custom_words(text, &custom_words, 0.3);
         assert_eq!(result, "the cat sat");
     }
+
+    #[test]
+    fn test_word_diff() {
+        let diff = word_diff(
+            "Send the report to Anna today.",
+            "send the reports to Ana today",
+        );
+        let ops: Vec<(DiffOp, &str)> = diff.iter().map(|c| (c.op, c.text.as_str())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Equal, "send the"),
+                (DiffOp::Delete, "report"),
+                (DiffOp::Insert, "reports"),
+                (DiffOp::Equal, "to"),
+                (DiffOp::Delete, "Anna"),
+                (DiffOp::Insert, "Ana"),
+                (DiffOp::Equal, "today"),
+            ]
+        );
+        assert!(word_diff("", "").is_empty());
+        assert_eq!(
+            word_diff("", "new words"),
+            vec![DiffChunk {
+                op: DiffOp::Insert,
+                text: "new words".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_initial_prompt() {
+        let vocabulary = vec![
+            "Tauri".to_string(),
+            " ".to_string(),
+            "Kubernetes".to_string(),
+        ];
+        assert_eq!(build_initial_prompt(&[], None), None);
+        assert_eq!(build_initial_prompt(&[], Some("  ")), None);
+        assert_eq!(
+            build_initial_prompt(&vocabulary, None).as_deref(),
+            Some("Tauri, Kubernetes.")
+        );
+        assert_eq!(
+            build_initial_prompt(&vocabulary, Some("we deploy it")).as_deref(),
+            Some("Tauri, Kubernetes. we deploy it")
+        );
+
+        // Long vocabulary and context stay within budget, keeping the latest context
+        let many: Vec<String> = (0..200).map(|i| format!("term{}", i)).collect();
+        let context = "word ".repeat(300) + "the end";
+        let prompt = build_initial_prompt(&many, Some(&context)).unwrap();
+        assert!(prompt.len() <= MAX_INITIAL_PROMPT_CHARS);
+        assert!(prompt.starts_with("term0, term1,"));
+        assert!(prompt.ends_with("word the end"));
+        assert!(!prompt.contains("term199"));
+    }
 }
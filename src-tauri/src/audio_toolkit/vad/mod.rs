@@ -44,6 +44,8 @@ pub trait VoiceActivityDetector: Send + Sync {
 
 mod silero;
 mod smoothed;
+mod split;
 
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
+pub use split::split_on_silence;
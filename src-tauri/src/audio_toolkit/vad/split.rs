@@ -0,0 +1,128 @@
+use anyhow::Result;
+use std::ops::Range;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// Samples per VAD frame (30 ms at 16 kHz)
+const FRAME_SAMPLES: usize = (WHISPER_SAMPLE_RATE * 30 / 1000) as usize;
+/// A pause this long (~0.6 s) ends a chunk
+const SPLIT_SILENCE_FRAMES: usize = 20;
+/// Audio kept around speech so words at chunk edges aren't clipped (~0.3 s)
+const PADDING_FRAMES: usize = 10;
+
+/// Splits 16 kHz audio into chunks of speech separated by silence, for transcribing
+/// long recordings piece by piece. Chunks longer than `max_chunk_samples` are cut
+/// regardless of speech. Stretches without any speech are left out.
+///
+/// `is_speech` is called once per 30 ms frame, in order (the last frame is zero-padded).
+pub fn split_on_silence(
+    samples: &[f32],
+    max_chunk_samples: usize,
+    mut is_speech: impl FnMut(&[f32]) -> Result<bool>,
+) -> Result<Vec<Range<usize>>> {
+    let padding = PADDING_FRAMES * FRAME_SAMPLES;
+    let mut chunks: Vec<Range<usize>> = Vec::new();
+    // Start of the open chunk and the end of its last speech frame
+    let mut open: Option<(usize, usize)> = None;
+    let mut silent_frames = 0;
+    let mut frame = vec![0.0; FRAME_SAMPLES];
+
+    for (index, samples_in_frame) in samples.chunks(FRAME_SAMPLES).enumerate() {
+        let frame_start = index * FRAME_SAMPLES;
+        let frame_end = frame_start + samples_in_frame.len();
+        frame[..samples_in_frame.len()].copy_from_slice(samples_in_frame);
+        frame[samples_in_frame.len()..].fill(0.0);
+
+        if is_speech(&frame)? {
+            silent_frames = 0;
+            match open.as_mut() {
+                Some((_, speech_end)) => *speech_end = frame_end,
+                None => {
+                    let previous_end = chunks.last().map_or(0, |c| c.end);
+                    let start = frame_start.saturating_sub(padding).max(previous_end);
+                    open = Some((start, frame_end));
+                }
+            }
+        } else {
+            silent_frames += 1;
+            if let Some((start, speech_end)) = open {
+                if silent_frames >= SPLIT_SILENCE_FRAMES {
+                    chunks.push(start..(speech_end + padding).min(frame_end));
+                    open = None;
+                }
+            }
+        }
+
+        // Hard cut for chunks that run past the engine's window
+        if let Some((start, _)) = open {
+            if frame_end - start >= max_chunk_samples {
+                chunks.push(start..frame_end);
+                open = None;
+            }
+        }
+    }
+
+    if let Some((start, speech_end)) = open {
+        chunks.push(start..(speech_end + padding).min(samples.len()));
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds audio from (seconds, is_speech) runs; speech is a constant 0.5 signal
+    fn audio(runs: &[(f32, bool)]) -> Vec<f32> {
+        runs.iter()
+            .flat_map(|&(seconds, speech)| {
+                let len = (seconds * WHISPER_SAMPLE_RATE as f32) as usize;
+                std::iter::repeat(if speech { 0.5 } else { 0.0 }).take(len)
+            })
+            .collect()
+    }
+
+    fn energy(frame: &[f32]) -> Result<bool> {
+        Ok(frame.iter().any(|s| s.abs() > 0.1))
+    }
+
+    #[test]
+    fn test_splits_at_pauses_and_skips_silence() {
+        let samples = audio(&[
+            (1.0, false),
+            (2.0, true),
+            (1.0, false),
+            (1.5, true),
+            (2.0, false),
+        ]);
+        let chunks = split_on_silence(&samples, 30 * 16000, energy).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        let padding = PADDING_FRAMES * FRAME_SAMPLES;
+        // Speech starts mid-frame at 1 s; the chunk opens one padding before that frame
+        assert_eq!(
+            chunks[0].start,
+            (16000 / FRAME_SAMPLES) * FRAME_SAMPLES - padding
+        );
+        assert_eq!(chunks[0].end, 3 * 16000 + padding);
+        assert!(chunks[1].start >= chunks[0].end);
+        assert!(chunks[1].end <= samples.len());
+    }
+
+    #[test]
+    fn test_short_pauses_do_not_split() {
+        let samples = audio(&[(1.0, true), (0.2, false), (1.0, true)]);
+        let chunks = split_on_silence(&samples, 30 * 16000, energy).unwrap();
+        assert_eq!(chunks, vec![0..samples.len()]);
+    }
+
+    #[test]
+    fn test_long_speech_is_cut_at_max_length() {
+        let samples = audio(&[(5.0, true)]);
+        let chunks = split_on_silence(&samples, 2 * 16000, energy).unwrap();
+        assert!(chunks.len() >= 2);
+        assert!(chunks.iter().all(|c| c.len() <= 2 * 16000 + FRAME_SAMPLES));
+        assert_eq!(chunks.last().unwrap().end, samples.len());
+    }
+}
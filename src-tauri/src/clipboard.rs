@@ -3,30 +3,27 @@ use enigo::Enigo;
 use enigo::Key;
 use enigo::Keyboard;
 use enigo::Settings;
+use log::{debug, warn};
+use tauri::image::Image;
 use tauri::AppHandle;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
-/// Sends a paste command (Cmd+V or Ctrl+V) using platform-specific virtual key codes.
-/// This ensures the paste works regardless of keyboard layout (e.g., Russian, AZERTY, DVORAK).
-fn send_paste() -> Result<(), String> {
-    // Platform-specific key definitions
+/// Sends modifier + key (e.g. Cmd+V / Ctrl+V) using the given virtual key.
+fn send_modified_key(key: Key) -> Result<(), String> {
     #[cfg(target_os = "macos")]
-    let (modifier_key, v_key_code) = (Key::Meta, Key::Other(9));
-    #[cfg(target_os = "windows")]
-    let (modifier_key, v_key_code) = (Key::Control, Key::Other(0x56)); // VK_V
-    #[cfg(target_os = "linux")]
-    let (modifier_key, v_key_code) = (Key::Control, Key::Unicode('v'));
+    let modifier_key = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let modifier_key = Key::Control;
 
     let mut enigo = Enigo::new(&Settings::default())
         .map_err(|e| format!("Failed to initialize Enigo: {}", e))?;
 
-    // Press modifier + V
     enigo
         .key(modifier_key, enigo::Direction::Press)
         .map_err(|e| format!("Failed to press modifier key: {}", e))?;
     enigo
-        .key(v_key_code, enigo::Direction::Click)
-        .map_err(|e| format!("Failed to click V key: {}", e))?;
+        .key(key, enigo::Direction::Click)
+        .map_err(|e| format!("Failed to click key: {}", e))?;
 
     std::thread::sleep(std::time::Duration::from_millis(100));
 
@@ -37,6 +34,47 @@ fn send_paste() -> Result<(), String> {
     Ok(())
 }
 
+/// Sends a paste command (Cmd+V or Ctrl+V) using platform-specific virtual key codes.
+/// This ensures the paste works regardless of keyboard layout (e.g., Russian, AZERTY, DVORAK).
+fn send_paste() -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let v_key_code = Key::Other(9); // kVK_ANSI_V
+    #[cfg(target_os = "windows")]
+    let v_key_code = Key::Other(0x56); // VK_V
+    #[cfg(target_os = "linux")]
+    let v_key_code = Key::Unicode('v');
+
+    send_modified_key(v_key_code)
+}
+
+/// Sends a copy command (Cmd+C or Ctrl+C), layout-independent like `send_paste`.
+///
+/// Selection capture runs while the user is still holding the shortcut, so extra modifiers
+/// are released first; otherwise Ctrl+Shift+Space turns the copy into Ctrl+Shift+C
+/// (which opens the element picker in browsers).
+fn send_copy() -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Failed to initialize Enigo: {}", e))?;
+    #[cfg(target_os = "macos")]
+    let held_modifiers = [Key::Shift, Key::Alt, Key::Control];
+    #[cfg(not(target_os = "macos"))]
+    let held_modifiers = [Key::Shift, Key::Alt, Key::Meta];
+    for modifier in held_modifiers {
+        enigo
+            .key(modifier, enigo::Direction::Release)
+            .map_err(|e| format!("Failed to release modifier key: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    let c_key_code = Key::Other(8); // kVK_ANSI_C
+    #[cfg(target_os = "windows")]
+    let c_key_code = Key::Other(0x43); // VK_C
+    #[cfg(target_os = "linux")]
+    let c_key_code = Key::Unicode('c');
+
+    send_modified_key(c_key_code)
+}
+
 /// Pastes text directly using the enigo text method.
 /// This tries to use system input methods if possible, otherwise simulates keystrokes one by one.
 fn paste_via_direct_input(text: &str) -> Result<(), String> {
@@ -100,3 +138,80 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Clipboard contents saved around a simulated copy
+enum ClipboardSnapshot {
+    Text(String),
+    Image(Image<'static>),
+}
+
+/// Saves the clipboard if it can be put back exactly. The clipboard plugin only reads
+/// and writes text and images, one at a time, so None is returned when the clipboard
+/// holds both, or anything else (files, rich text without plain text), or nothing.
+fn snapshot_clipboard(app_handle: &AppHandle) -> Option<ClipboardSnapshot> {
+    let clipboard = app_handle.clipboard();
+    match (clipboard.read_text(), clipboard.read_image()) {
+        (Ok(text), Err(_)) => Some(ClipboardSnapshot::Text(text)),
+        (Err(_), Ok(image)) => Some(ClipboardSnapshot::Image(image.to_owned())),
+        _ => None,
+    }
+}
+
+fn restore_clipboard(app_handle: &AppHandle, snapshot: ClipboardSnapshot) -> Result<(), String> {
+    let clipboard = app_handle.clipboard();
+    match snapshot {
+        ClipboardSnapshot::Text(text) => clipboard.write_text(text),
+        ClipboardSnapshot::Image(image) => clipboard.write_image(&image),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Copies the current selection of the focused app and returns it, leaving the
+/// clipboard as it was. Returns None when nothing is selected, or when the clipboard
+/// holds data that couldn't be restored after the copy, which is then left alone.
+///
+/// Must run while the target app still has focus (i.e. before the overlay is shown).
+pub fn capture_selection(app_handle: &AppHandle) -> Option<String> {
+    let Some(previous) = snapshot_clipboard(app_handle) else {
+        debug!("Clipboard contents can't be restored, not copying the selection");
+        return None;
+    };
+    let clipboard = app_handle.clipboard();
+
+    // Write a marker first so an empty selection (copy is a no-op) is distinguishable
+    // from a selection that happens to equal the old clipboard contents
+    let marker = format!("leadrscribe-selection-{}", std::process::id());
+    if let Err(e) = clipboard.write_text(&marker) {
+        warn!("Failed to prepare clipboard for selection capture: {}", e);
+        return None;
+    }
+
+    if let Err(e) = send_copy() {
+        warn!("Failed to copy selection: {}", e);
+    }
+
+    // Give the focused app time to service the copy
+    std::thread::sleep(std::time::Duration::from_millis(150));
+    let copied = clipboard.read_text().unwrap_or_default();
+
+    if let Err(e) = restore_clipboard(app_handle, previous) {
+        warn!("Failed to restore clipboard after selection capture: {}", e);
+    }
+
+    if copied == marker || copied.trim().is_empty() {
+        None
+    } else {
+        Some(copied)
+    }
+}
+
+/// Text the user is replying to: the current selection, or the clipboard if nothing is selected.
+pub fn capture_reply_context(app_handle: &AppHandle) -> Option<String> {
+    capture_selection(app_handle).or_else(|| {
+        app_handle
+            .clipboard()
+            .read_text()
+            .ok()
+            .filter(|text| !text.trim().is_empty())
+    })
+}
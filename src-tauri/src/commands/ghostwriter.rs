@@ -0,0 +1,19 @@
+use tauri::AppHandle;
+
+use crate::ghostwriter;
+use crate::settings::{self, GhostwriterProviderKind};
+
+/// Lists models offered by a ghostwriter provider (defaults to the one selected in settings)
+#[tauri::command]
+pub async fn list_ghostwriter_models(
+    app: AppHandle,
+    provider: Option<String>,
+) -> Result<Vec<String>, String> {
+    let settings = settings::get_settings(&app);
+    let kind = provider.as_deref().and_then(GhostwriterProviderKind::parse);
+    let (provider, _) = ghostwriter::resolve_provider(&settings, kind);
+
+    ghostwriter::list_models(provider.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,4 +1,10 @@
-use crate::managers::history::{HistoryEntry, HistoryManager, HistoryStats};
+use crate::audio_toolkit::{decode_audio_file, word_diff, DiffChunk};
+use crate::managers::history::{HistoryEntry, HistoryManager, HistoryStats, TranscriptionVersion};
+use crate::managers::transcription::{
+    TranscriptSegment, TranscriptionManager, TranscriptionOverrides,
+};
+use crate::subtitles::SubtitleFormat;
+use serde::Serialize;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -37,6 +43,120 @@ pub async fn get_audio_file_path(
         .map(|s| s.to_string())
 }
 
+#[tauri::command]
+pub async fn get_transcription_segments(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    history_id: i64,
+) -> Result<Vec<TranscriptSegment>, String> {
+    history_manager
+        .get_transcription_segments(history_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes SRT or WebVTT subtitles for an entry and returns the file path
+#[tauri::command]
+pub async fn export_subtitles(
+    app: AppHandle,
+    history_id: i64,
+    format: SubtitleFormat,
+) -> Result<String, String> {
+    let path = crate::subtitles::export_subtitles(&app, history_id, format)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// A transcript version and its word diff against the entry's original transcription
+#[derive(Serialize)]
+pub struct VersionComparison {
+    pub version: TranscriptionVersion,
+    pub diff: Vec<DiffChunk>,
+}
+
+/// Re-runs an entry's recording through `model_id` and stores the result as a new version
+#[tauri::command]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    history_id: i64,
+    model_id: String,
+    language: Option<String>,
+    translate: Option<bool>,
+) -> Result<VersionComparison, String> {
+    let entry = history_manager
+        .get_entry_by_id(history_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", history_id))?;
+
+    let translate =
+        translate.unwrap_or_else(|| crate::settings::get_settings(&app).translate_to_english);
+    let overrides = TranscriptionOverrides {
+        language: language.clone(),
+        translate: Some(translate),
+    };
+    let audio_path = history_manager.get_audio_file_path(&entry.file_name);
+    let tm = Arc::clone(&transcription_manager);
+    let model = model_id.clone();
+    let text = tauri::async_runtime::spawn_blocking(move || -> anyhow::Result<String> {
+        let samples = decode_audio_file(&audio_path)?;
+        tm.transcribe_with_model(&model, samples, &overrides)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Re-transcription failed: {}", e))?;
+
+    let version = history_manager
+        .add_transcription_version(history_id, &model_id, language.as_deref(), translate, &text)
+        .map_err(|e| e.to_string())?;
+
+    Ok(VersionComparison {
+        diff: word_diff(&entry.transcription_text, &version.transcription_text),
+        version,
+    })
+}
+
+#[tauri::command]
+pub async fn get_transcription_versions(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    history_id: i64,
+) -> Result<Vec<VersionComparison>, String> {
+    let entry = history_manager
+        .get_entry_by_id(history_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", history_id))?;
+
+    let versions = history_manager
+        .get_transcription_versions(history_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(versions
+        .into_iter()
+        .map(|version| VersionComparison {
+            diff: word_diff(&entry.transcription_text, &version.transcription_text),
+            version,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn delete_transcription_version(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<(), String> {
+    history_manager
+        .delete_transcription_version(id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn delete_history_entry(
     _app: AppHandle,
@@ -136,8 +256,5 @@ pub async fn get_history_stats(
     _app: AppHandle,
     history_manager: State<'_, Arc<HistoryManager>>,
 ) -> Result<HistoryStats, String> {
-    history_manager
-        .get_stats()
-        .await
-        .map_err(|e| e.to_string())
+    history_manager.get_stats().await.map_err(|e| e.to_string())
 }
@@ -0,0 +1,51 @@
+use crate::managers::import::{ImportManager, TranscriptionJob};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+
+/// Transcribes a WAV/FLAC/MP3/OGG file into a new history entry and returns its id
+#[tauri::command]
+pub async fn import_audio_file(
+    import_manager: State<'_, Arc<ImportManager>>,
+    path: String,
+) -> Result<i64, String> {
+    import_manager
+        .import_file(PathBuf::from(path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Queues files and folders for background transcription into history
+#[tauri::command]
+pub fn enqueue_transcription_jobs(
+    import_manager: State<'_, Arc<ImportManager>>,
+    paths: Vec<String>,
+) -> Result<Vec<TranscriptionJob>, String> {
+    import_manager
+        .enqueue(paths.into_iter().map(PathBuf::from).collect())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_transcription_jobs(
+    import_manager: State<'_, Arc<ImportManager>>,
+) -> Result<Vec<TranscriptionJob>, String> {
+    import_manager.get_jobs().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_transcription_job(
+    import_manager: State<'_, Arc<ImportManager>>,
+    id: i64,
+) -> Result<(), String> {
+    import_manager.cancel_job(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clear_finished_transcription_jobs(
+    import_manager: State<'_, Arc<ImportManager>>,
+) -> Result<(), String> {
+    import_manager
+        .clear_finished_jobs()
+        .map_err(|e| e.to_string())
+}
@@ -1,5 +1,7 @@
 pub mod audio;
+pub mod ghostwriter;
 pub mod history;
+pub mod import;
 pub mod models;
 pub mod profile;
 pub mod settings;
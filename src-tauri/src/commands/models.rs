@@ -77,6 +77,35 @@ pub async fn set_active_model(
     Ok(())
 }
 
+/// Picks a separate model for live streaming windows; None streams with the active model.
+/// The new model is loaded when the next recording starts.
+#[tauri::command]
+pub async fn set_streaming_model(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_id: Option<String>,
+) -> Result<(), String> {
+    let model_id = model_id.filter(|id| !id.is_empty());
+    if let Some(model_id) = &model_id {
+        let model_info = model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| format!("Model not found: {}", model_id))?;
+
+        if !model_info.is_downloaded {
+            return Err(format!("Model not downloaded: {}", model_id));
+        }
+    }
+
+    transcription_manager.unload_streaming_model();
+
+    let mut settings = get_settings(&app_handle);
+    settings.streaming_model = model_id;
+    write_settings(&app_handle, settings);
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_current_model(app_handle: AppHandle) -> Result<String, String> {
     let settings = get_settings(&app_handle);
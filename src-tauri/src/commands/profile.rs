@@ -1,8 +1,107 @@
+use crate::ghostwriter::template;
 use crate::managers::history::Profile;
-use crate::managers::profile::ProfileManager;
+use crate::managers::profile::{ProfileExample, ProfileManager};
+use crate::settings::{DecodingParams, GhostwriterProviderKind};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
+const MAX_PROFILE_MAX_TOKENS: i64 = 32_000;
+/// Longest dictation or output accepted for a single few-shot example
+const MAX_EXAMPLE_CHARS: usize = 4000;
+const MAX_VOCABULARY_TERMS: usize = 200;
+const MAX_VOCABULARY_TERM_CHARS: usize = 100;
+
+/// Empty string means "use the global provider"; anything else must be a known provider id.
+fn validate_ghostwriter_provider(provider: Option<String>) -> Result<Option<String>, String> {
+    match provider.filter(|p| !p.is_empty()) {
+        Some(p) => GhostwriterProviderKind::parse(&p)
+            .map(|kind| Some(kind.as_str().to_string()))
+            .ok_or_else(|| format!("Unknown ghostwriter provider '{}'", p)),
+        None => Ok(None),
+    }
+}
+
+/// Range checks for the per-profile generation overrides; a blank model means "use the
+/// provider's model".
+fn validate_generation_params(
+    ghostwriter_model: Option<String>,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    max_tokens: Option<i64>,
+) -> Result<Option<String>, String> {
+    if let Some(t) = temperature {
+        if !(0.0..=2.0).contains(&t) {
+            return Err(format!("Temperature must be between 0 and 2 (got {})", t));
+        }
+    }
+    if let Some(p) = top_p {
+        if p.is_nan() || p <= 0.0 || p > 1.0 {
+            return Err(format!(
+                "Top P must be greater than 0 and at most 1 (got {})",
+                p
+            ));
+        }
+    }
+    if let Some(m) = max_tokens {
+        if !(1..=MAX_PROFILE_MAX_TOKENS).contains(&m) {
+            return Err(format!(
+                "Max tokens must be between 1 and {} (got {})",
+                MAX_PROFILE_MAX_TOKENS, m
+            ));
+        }
+    }
+    Ok(ghostwriter_model
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty()))
+}
+
+/// Both sides of a few-shot example must be present and reasonably short.
+fn validate_example(input: &str, output: &str) -> Result<(), String> {
+    if input.trim().is_empty() || output.trim().is_empty() {
+        return Err("Examples need both a dictation and the desired output".to_string());
+    }
+    if input.chars().count() > MAX_EXAMPLE_CHARS || output.chars().count() > MAX_EXAMPLE_CHARS {
+        return Err(format!(
+            "Examples are limited to {} characters per side",
+            MAX_EXAMPLE_CHARS
+        ));
+    }
+    Ok(())
+}
+
+/// Trims terms, drops blanks and case-insensitive duplicates, and enforces the size limits.
+fn normalize_vocabulary(vocabulary: Option<Vec<String>>) -> Result<Vec<String>, String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in vocabulary.unwrap_or_default() {
+        let term = term.trim();
+        if term.is_empty() || terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            continue;
+        }
+        if term.chars().count() > MAX_VOCABULARY_TERM_CHARS {
+            return Err(format!(
+                "Vocabulary terms are limited to {} characters",
+                MAX_VOCABULARY_TERM_CHARS
+            ));
+        }
+        terms.push(term.to_string());
+    }
+    if terms.len() > MAX_VOCABULARY_TERMS {
+        return Err(format!(
+            "Profiles can have at most {} vocabulary terms",
+            MAX_VOCABULARY_TERMS
+        ));
+    }
+    Ok(terms)
+}
+
+/// Custom instructions may only reference known template variables such as `{date}`.
+fn validate_custom_instructions(custom_instructions: Option<&str>) -> Result<(), String> {
+    match custom_instructions {
+        Some(instructions) => template::validate(instructions),
+        None => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_profiles(
     _app: AppHandle,
@@ -27,6 +126,7 @@ pub async fn get_profile(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_profile(
     _app: AppHandle,
     profile_manager: State<'_, Arc<ProfileManager>>,
@@ -35,14 +135,42 @@ pub async fn create_profile(
     color: String,
     icon: String,
     custom_instructions: Option<String>,
+    ghostwriter_provider: Option<String>,
+    ghostwriter_model: Option<String>,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    max_tokens: Option<i64>,
+    decoding_params: Option<DecodingParams>,
+    vocabulary: Option<Vec<String>>,
 ) -> Result<i64, String> {
+    validate_custom_instructions(custom_instructions.as_deref())?;
+    let ghostwriter_provider = validate_ghostwriter_provider(ghostwriter_provider)?;
+    let ghostwriter_model =
+        validate_generation_params(ghostwriter_model, temperature, top_p, max_tokens)?;
+    let decoding_params = decoding_params.unwrap_or_default();
+    decoding_params.validate()?;
+    let vocabulary = normalize_vocabulary(vocabulary)?;
     profile_manager
-        .create_profile(name, description, color, icon, custom_instructions)
+        .create_profile(
+            name,
+            description,
+            color,
+            icon,
+            custom_instructions,
+            ghostwriter_provider,
+            ghostwriter_model,
+            temperature,
+            top_p,
+            max_tokens,
+            &decoding_params,
+            &vocabulary,
+        )
         .await
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn update_profile(
     _app: AppHandle,
     profile_manager: State<'_, Arc<ProfileManager>>,
@@ -52,9 +180,37 @@ pub async fn update_profile(
     color: String,
     icon: String,
     custom_instructions: Option<String>,
+    ghostwriter_provider: Option<String>,
+    ghostwriter_model: Option<String>,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    max_tokens: Option<i64>,
+    decoding_params: Option<DecodingParams>,
+    vocabulary: Option<Vec<String>>,
 ) -> Result<(), String> {
+    validate_custom_instructions(custom_instructions.as_deref())?;
+    let ghostwriter_provider = validate_ghostwriter_provider(ghostwriter_provider)?;
+    let ghostwriter_model =
+        validate_generation_params(ghostwriter_model, temperature, top_p, max_tokens)?;
+    let decoding_params = decoding_params.unwrap_or_default();
+    decoding_params.validate()?;
+    let vocabulary = normalize_vocabulary(vocabulary)?;
     profile_manager
-        .update_profile(id, name, description, color, icon, custom_instructions)
+        .update_profile(
+            id,
+            name,
+            description,
+            color,
+            icon,
+            custom_instructions,
+            ghostwriter_provider,
+            ghostwriter_model,
+            temperature,
+            top_p,
+            max_tokens,
+            &decoding_params,
+            &vocabulary,
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -94,3 +250,61 @@ pub async fn get_profile_stats(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_profile_examples(
+    _app: AppHandle,
+    profile_manager: State<'_, Arc<ProfileManager>>,
+    profile_id: i64,
+) -> Result<Vec<ProfileExample>, String> {
+    profile_manager
+        .get_examples(profile_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_profile_example(
+    _app: AppHandle,
+    profile_manager: State<'_, Arc<ProfileManager>>,
+    profile_id: i64,
+    input: String,
+    output: String,
+) -> Result<i64, String> {
+    validate_example(&input, &output)?;
+    profile_manager
+        .add_example(
+            profile_id,
+            input.trim().to_string(),
+            output.trim().to_string(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_profile_example(
+    _app: AppHandle,
+    profile_manager: State<'_, Arc<ProfileManager>>,
+    id: i64,
+    input: String,
+    output: String,
+) -> Result<(), String> {
+    validate_example(&input, &output)?;
+    profile_manager
+        .update_example(id, input.trim().to_string(), output.trim().to_string())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_profile_example(
+    _app: AppHandle,
+    profile_manager: State<'_, Arc<ProfileManager>>,
+    id: i64,
+) -> Result<(), String> {
+    profile_manager
+        .delete_example(id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -2,7 +2,10 @@ use log::{debug, warn};
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_autostart::ManagerExt;
 
-use crate::settings::{self, ClipboardHandling, OverlayPosition, PasteMethod, SoundTheme};
+use crate::ghostwriter::template;
+use crate::settings::{
+    self, ClipboardHandling, DecodingParams, OverlayPosition, PasteMethod, SoundTheme,
+};
 
 #[tauri::command]
 pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -66,6 +69,23 @@ pub fn change_selected_language_setting(app: AppHandle, language: String) -> Res
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_allowed_languages_setting(
+    app: AppHandle,
+    languages: Vec<String>,
+) -> Result<(), String> {
+    let mut allowed: Vec<String> = Vec::new();
+    for language in languages.iter().map(|l| l.trim()) {
+        if !language.is_empty() && language != "auto" && !allowed.iter().any(|l| l == language) {
+            allowed.push(language.to_string());
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.allowed_languages = allowed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
@@ -191,7 +211,10 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
         "dont_modify" => ClipboardHandling::DontModify,
         "copy_to_clipboard" => ClipboardHandling::CopyToClipboard,
         other => {
-            warn!("Invalid clipboard handling '{}', defaulting to dont_modify", other);
+            warn!(
+                "Invalid clipboard handling '{}', defaulting to dont_modify",
+                other
+            );
             ClipboardHandling::DontModify
         }
     };
@@ -215,6 +238,7 @@ pub fn change_output_mode_setting(app: AppHandle, mode: String) -> Result<(), St
     let parsed = match mode.as_str() {
         "transcript" => settings::OutputMode::Transcript,
         "ghostwriter" => settings::OutputMode::Ghostwriter,
+        "reply" => settings::OutputMode::Reply,
         other => {
             warn!("Invalid output mode '{}', defaulting to transcript", other);
             settings::OutputMode::Transcript
@@ -250,7 +274,10 @@ pub fn get_openrouter_api_key_setting(app: AppHandle) -> Option<String> {
 }
 
 #[tauri::command]
-pub fn change_openrouter_api_key_setting(app: AppHandle, api_key: Option<String>) -> Result<(), String> {
+pub fn change_openrouter_api_key_setting(
+    app: AppHandle,
+    api_key: Option<String>,
+) -> Result<(), String> {
     let mut s = settings::get_settings(&app);
 
     if let Some(ref key) = api_key {
@@ -286,9 +313,218 @@ pub fn change_openrouter_model_setting(app: AppHandle, model: String) -> Result<
 }
 
 #[tauri::command]
-pub fn change_custom_instructions_setting(app: AppHandle, instructions: String) -> Result<(), String> {
+pub fn change_openrouter_fallback_models_setting(
+    app: AppHandle,
+    models: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openrouter_fallback_models = clean_model_list(models);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Trims model ids and drops blank entries
+fn clean_model_list(models: Vec<String>) -> Vec<String> {
+    models
+        .into_iter()
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect()
+}
+
+#[tauri::command]
+pub fn change_custom_instructions_setting(
+    app: AppHandle,
+    instructions: String,
+) -> Result<(), String> {
+    template::validate(&instructions)?;
     let mut settings = settings::get_settings(&app);
     settings.custom_instructions = instructions;
     settings::write_settings(&app, settings);
     Ok(())
 }
+
+#[tauri::command]
+pub fn change_user_name_setting(app: AppHandle, user_name: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.user_name = user_name.trim().to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_provider_setting(app: AppHandle, provider: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match settings::GhostwriterProviderKind::parse(&provider) {
+        Some(kind) => kind,
+        None => {
+            warn!(
+                "Invalid ghostwriter provider '{}', defaulting to openrouter",
+                provider
+            );
+            settings::GhostwriterProviderKind::OpenRouter
+        }
+    };
+    settings.ghostwriter_provider = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_streaming_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.ghostwriter_streaming = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_max_retries_setting(app: AppHandle, retries: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.ghostwriter_max_retries = retries.min(5);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_guardrails_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.ghostwriter_guardrails = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_accurate_final_pass_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.accurate_final_pass = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_long_form_dictation_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.long_form_dictation = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_low_confidence_threshold_setting(
+    app: AppHandle,
+    threshold: f64,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Confidence threshold must be between 0 and 1".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.low_confidence_threshold = threshold;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_verify_hints_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.ghostwriter_verify_hints = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_ghostwriter_monthly_budget_setting(
+    app: AppHandle,
+    budget: Option<f64>,
+) -> Result<(), String> {
+    if let Some(budget) = budget {
+        if !budget.is_finite() || budget < 0.0 {
+            return Err("Monthly budget must be a positive amount".to_string());
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.ghostwriter_monthly_budget = budget;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_decoding_params_setting(
+    app: AppHandle,
+    params: DecodingParams,
+) -> Result<(), String> {
+    params.validate()?;
+    let mut settings = settings::get_settings(&app);
+    settings.decoding_params = params;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_compatible_base_url_setting(
+    app: AppHandle,
+    base_url: String,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_compatible_base_url = base_url.trim().to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_compatible_model_setting(app: AppHandle, model: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_compatible_model = model;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_compatible_fallback_models_setting(
+    app: AppHandle,
+    models: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_compatible_fallback_models = clean_model_list(models);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_openai_compatible_api_key_setting(app: AppHandle) -> Option<String> {
+    settings::get_openai_compatible_api_key()
+        .or_else(|| settings::get_settings(&app).openai_compatible_api_key)
+        .filter(|key| !key.is_empty())
+}
+
+#[tauri::command]
+pub fn change_openai_compatible_api_key_setting(
+    app: AppHandle,
+    api_key: Option<String>,
+) -> Result<(), String> {
+    let mut s = settings::get_settings(&app);
+
+    match api_key.filter(|key| !key.is_empty()) {
+        Some(key) => {
+            let keyring_works = settings::set_openai_compatible_api_key(&key).is_ok()
+                && settings::get_openai_compatible_api_key().as_deref() == Some(key.as_str());
+
+            if keyring_works {
+                s.openai_compatible_api_key = None;
+            } else {
+                warn!("Keychain storage failed or unverifiable, falling back to settings file");
+                s.openai_compatible_api_key = Some(key);
+            }
+        }
+        None => {
+            let _ = settings::delete_openai_compatible_api_key();
+            s.openai_compatible_api_key = None;
+        }
+    }
+
+    settings::write_settings(&app, s);
+    Ok(())
+}
@@ -1,5 +1,7 @@
+use crate::managers::inference_queue::QueueMetrics;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings, ModelUnloadTimeout};
+use std::sync::Arc;
 use tauri::{AppHandle, State};
 
 #[tauri::command]
@@ -30,3 +32,10 @@ pub fn unload_model_manually(
         .unload_model()
         .map_err(|e| format!("Failed to unload model: {}", e))
 }
+
+#[tauri::command]
+pub fn get_transcription_queue_metrics(
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> QueueMetrics {
+    transcription_manager.queue_metrics()
+}
@@ -0,0 +1,109 @@
+pub mod parakeet;
+pub mod whisper;
+
+use crate::language::DetectedLanguage;
+use crate::managers::model::EngineType;
+use crate::settings::DecodingParams;
+use anyhow::Result;
+use std::path::Path;
+
+/// What an engine can do beyond plain transcription
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EngineCapabilities {
+    /// Accepts an initial prompt to bias vocabulary and style
+    pub supports_prompt: bool,
+    /// Accepts a spoken language instead of detecting it
+    pub supports_language: bool,
+    /// Can translate to English while transcribing
+    pub supports_translate: bool,
+    /// Reports segment timestamps
+    pub supports_timestamps: bool,
+    /// Timestamped output has one segment per word rather than per sentence
+    pub word_timestamps: bool,
+}
+
+/// Options for one transcription run. Engines ignore what their capabilities don't cover.
+#[derive(Clone, Debug, Default)]
+pub struct EngineOptions {
+    /// Whisper language code, or None to auto-detect
+    pub language: Option<String>,
+    /// Language codes auto-detection may choose from; empty allows any
+    pub allowed_languages: Vec<String>,
+    pub translate: bool,
+    pub prompt: Option<String>,
+    pub timestamps: bool,
+    pub decoding: DecodingParams,
+}
+
+/// A stretch of recognised text, in seconds from the start of the audio
+#[derive(Clone, Debug, PartialEq)]
+pub struct EngineSegment {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    /// Mean token probability from 0 to 1, when the engine reports one
+    pub confidence: Option<f32>,
+    /// Confidence of each whitespace-separated word of `text`; empty when the engine
+    /// doesn't report per-word probabilities
+    pub word_confidences: Vec<f32>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EngineTranscription {
+    pub text: String,
+    /// Empty unless timestamps were requested and supported
+    pub segments: Vec<EngineSegment>,
+    /// Language the audio was transcribed in: the requested one, or the one the engine
+    /// identified. None when the engine doesn't identify languages.
+    pub language: Option<DetectedLanguage>,
+}
+
+/// A speech-to-text backend that the transcription manager can load and run
+pub trait SpeechEngine: Send {
+    fn name(&self) -> &str;
+
+    fn capabilities(&self) -> EngineCapabilities;
+
+    /// Checks this machine can run the engine, explaining why not
+    fn check_supported(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()>;
+
+    fn unload(&mut self);
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        options: &EngineOptions,
+    ) -> Result<EngineTranscription>;
+}
+
+/// Creates an unloaded engine for a model catalog entry
+pub fn create_engine(engine_type: &EngineType) -> Box<dyn SpeechEngine> {
+    match engine_type {
+        EngineType::Whisper => Box::new(whisper::WhisperSpeechEngine::new()),
+        EngineType::Parakeet => Box::new(parakeet::ParakeetSpeechEngine::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_maps_catalog_engines() {
+        let whisper = create_engine(&EngineType::Whisper);
+        assert_eq!(whisper.name(), "Whisper");
+        assert!(whisper.capabilities().supports_prompt);
+        assert!(whisper.capabilities().supports_translate);
+        assert!(!whisper.capabilities().word_timestamps);
+
+        let parakeet = create_engine(&EngineType::Parakeet);
+        assert_eq!(parakeet.name(), "Parakeet");
+        assert!(!parakeet.capabilities().supports_prompt);
+        assert!(!parakeet.capabilities().supports_language);
+        assert!(parakeet.capabilities().word_timestamps);
+    }
+}
@@ -0,0 +1,107 @@
+use super::{EngineCapabilities, EngineOptions, EngineSegment, EngineTranscription, SpeechEngine};
+use crate::cpu_features;
+use anyhow::Result;
+use std::path::Path;
+use transcribe_rs::{
+    engines::parakeet::{
+        ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
+    },
+    TranscriptionEngine,
+};
+
+/// NVIDIA Parakeet models run through ONNX Runtime
+pub struct ParakeetSpeechEngine {
+    engine: ParakeetEngine,
+}
+
+impl ParakeetSpeechEngine {
+    pub fn new() -> Self {
+        Self {
+            engine: ParakeetEngine::new(),
+        }
+    }
+}
+
+impl Default for ParakeetSpeechEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeechEngine for ParakeetSpeechEngine {
+    fn name(&self) -> &str {
+        "Parakeet"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            supports_prompt: false,
+            supports_language: false,
+            supports_translate: false,
+            supports_timestamps: true,
+            word_timestamps: true,
+        }
+    }
+
+    fn check_supported(&self) -> Result<()> {
+        // ONNX Runtime is compiled with AVX/AVX2 instructions and will crash
+        // with STATUS_ILLEGAL_INSTRUCTION on CPUs without these features
+        if cpu_features::supports_parakeet() {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "Cannot load Parakeet model: CPU does not support required AVX2 instructions. \
+             Your processor lacks the AVX/AVX2 features required by ONNX Runtime. \
+             Please use Whisper models instead (e.g., whisper-small, whisper-medium)."
+        ))
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        self.engine
+            .load_model_with_params(model_path, ParakeetModelParams::int8())
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    fn unload(&mut self) {
+        self.engine.unload_model();
+    }
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        options: &EngineOptions,
+    ) -> Result<EngineTranscription> {
+        // Word granularity gives one timestamped segment per word
+        let params = ParakeetInferenceParams {
+            timestamp_granularity: if options.timestamps {
+                TimestampGranularity::Word
+            } else {
+                TimestampGranularity::Segment
+            },
+            ..Default::default()
+        };
+
+        let result = self
+            .engine
+            .transcribe_samples(audio, Some(params))
+            .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?;
+
+        Ok(EngineTranscription {
+            text: result.text,
+            segments: result
+                .segments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|segment| EngineSegment {
+                    text: segment.text,
+                    start: segment.start,
+                    end: segment.end,
+                    // transcribe-rs doesn't expose token probabilities yet
+                    confidence: None,
+                    word_confidences: Vec::new(),
+                })
+                .collect(),
+            language: None,
+        })
+    }
+}
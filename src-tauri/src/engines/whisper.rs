@@ -0,0 +1,329 @@
+use super::{EngineCapabilities, EngineOptions, EngineSegment, EngineTranscription, SpeechEngine};
+use crate::language::DetectedLanguage;
+use crate::settings::DecodingParams;
+use anyhow::Result;
+use log::debug;
+use std::path::Path;
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
+
+/// Beams used when no beam size is configured
+const DEFAULT_BEAM_SIZE: i32 = 3;
+/// Candidates per temperature when decoding greedily without a configured best-of
+const DEFAULT_BEST_OF: i32 = 5;
+const DEFAULT_NO_SPEECH_THRESHOLD: f32 = 0.2;
+
+/// Beam search unless the beam size is 1, or only best-of is set, which decode greedily
+fn sampling_strategy(params: &DecodingParams) -> SamplingStrategy {
+    match (params.beam_size, params.best_of) {
+        (Some(beam_size), _) if beam_size > 1 => SamplingStrategy::BeamSearch {
+            beam_size: beam_size as i32,
+            patience: -1.0,
+        },
+        (None, None) => SamplingStrategy::BeamSearch {
+            beam_size: DEFAULT_BEAM_SIZE,
+            patience: -1.0,
+        },
+        (_, best_of) => SamplingStrategy::Greedy {
+            best_of: best_of.map_or(DEFAULT_BEST_OF, |n| n as i32),
+        },
+    }
+}
+
+/// Mean probability of each whitespace-separated word, from the text and probability
+/// of its tokens in order. Tokens that start with whitespace begin a new word.
+fn word_confidences(tokens: &[(String, f32)]) -> Vec<f32> {
+    let mut words: Vec<(f32, usize)> = Vec::new();
+    let mut new_word = true;
+    for (text, probability) in tokens {
+        if text.starts_with(char::is_whitespace) {
+            new_word = true;
+        }
+        if text.trim().is_empty() {
+            continue;
+        }
+        if new_word {
+            words.push((0.0, 0));
+            new_word = false;
+        }
+        if let Some((sum, count)) = words.last_mut() {
+            *sum += probability;
+            *count += 1;
+        }
+        if text.ends_with(char::is_whitespace) {
+            new_word = true;
+        }
+    }
+    words
+        .into_iter()
+        .map(|(sum, count)| sum / count as f32)
+        .collect()
+}
+
+/// The language Whisper's language identification found most likely, with its
+/// probability. A non-empty `allowed` list restricts the candidates.
+fn most_likely_language(probabilities: &[f32], allowed: &[String]) -> Option<DetectedLanguage> {
+    probabilities
+        .iter()
+        .enumerate()
+        .filter_map(|(id, probability)| Some((whisper_rs::get_lang_str(id as i32)?, *probability)))
+        .filter(|(code, _)| allowed.is_empty() || allowed.iter().any(|a| a == code))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(code, probability)| DetectedLanguage {
+            code: code.to_string(),
+            probability: Some(probability),
+        })
+}
+
+/// Runs Whisper's language identification on the start of `audio`
+fn identify_language(
+    state: &mut WhisperState,
+    audio: &[f32],
+    threads: usize,
+    allowed: &[String],
+) -> Result<Option<DetectedLanguage>> {
+    state
+        .pcm_to_mel(audio, threads)
+        .map_err(|e| anyhow::anyhow!("Failed to compute mel spectrogram: {}", e))?;
+    let (_, probabilities) = state
+        .lang_detect(0, threads)
+        .map_err(|e| anyhow::anyhow!("Whisper language detection failed: {}", e))?;
+    Ok(most_likely_language(&probabilities, allowed))
+}
+
+/// whisper.cpp GGML models
+pub struct WhisperSpeechEngine {
+    state: Option<WhisperState>,
+    context: Option<WhisperContext>,
+}
+
+impl WhisperSpeechEngine {
+    pub fn new() -> Self {
+        Self {
+            state: None,
+            context: None,
+        }
+    }
+}
+
+impl Default for WhisperSpeechEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeechEngine for WhisperSpeechEngine {
+    fn name(&self) -> &str {
+        "Whisper"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            supports_prompt: true,
+            supports_language: true,
+            supports_translate: true,
+            supports_timestamps: true,
+            word_timestamps: false,
+        }
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        let path = model_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Model path is not valid UTF-8: {:?}", model_path))?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("Failed to load Whisper model: {}", e))?;
+        let state = context
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to create Whisper state: {}", e))?;
+        self.state = Some(state);
+        self.context = Some(context);
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        self.state = None;
+        self.context = None;
+    }
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        options: &EngineOptions,
+    ) -> Result<EngineTranscription> {
+        let not_loaded = || anyhow::anyhow!("Whisper model is not loaded");
+        // Token ids from end-of-text up are special and timestamp tokens
+        let first_special = self.context.as_ref().ok_or_else(not_loaded)?.token_eot();
+        let state = self.state.as_mut().ok_or_else(not_loaded)?;
+        let decoding = &options.decoding;
+        // Unset uses whisper.cpp's default of up to 4 threads
+        let threads = decoding.threads.map_or_else(
+            || std::thread::available_parallelism().map_or(1, |n| n.get().min(4)),
+            |n| n as usize,
+        );
+
+        // Detection inside `full` considers every language and doesn't report how sure
+        // it is, so the language is identified here and then forced
+        let language = match &options.language {
+            Some(code) => Some(DetectedLanguage {
+                code: code.clone(),
+                probability: None,
+            }),
+            None => {
+                let detected =
+                    identify_language(state, &audio, threads, &options.allowed_languages)?;
+                debug!("Identified language: {:?}", detected);
+                detected
+            }
+        };
+
+        let mut params = FullParams::new(sampling_strategy(decoding));
+        params.set_language(language.as_ref().map(|l| l.code.as_str()));
+        params.set_n_threads(threads as i32);
+        params.set_translate(options.translate);
+        if let Some(prompt) = &options.prompt {
+            params.set_initial_prompt(prompt);
+        }
+        if let Some(temperature) = decoding.temperature {
+            params.set_temperature(temperature);
+        }
+        if let Some(increment) = decoding.temperature_increment {
+            params.set_temperature_inc(increment);
+        }
+        params.set_no_speech_thold(
+            decoding
+                .no_speech_threshold
+                .unwrap_or(DEFAULT_NO_SPEECH_THRESHOLD),
+        );
+        // Speed optimizations
+        params.set_suppress_blank(true); // Skip blank segments for speed
+        params.set_suppress_nst(true); // Skip non-speech tokens
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+
+        state
+            .full(params, &audio)
+            .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+
+        let mut text = String::new();
+        let mut segments = Vec::new();
+        for segment in state.as_iter() {
+            let segment_text = segment.to_str_lossy()?.into_owned();
+            text.push_str(&segment_text);
+            if options.timestamps {
+                let tokens: Vec<(String, f32)> = (0..segment.n_tokens())
+                    .filter_map(|i| segment.get_token(i))
+                    .filter(|token| token.token_id() < first_special)
+                    .map(|token| {
+                        let text = token.to_str_lossy().map(|t| t.into_owned());
+                        (text.unwrap_or_default(), token.token_probability())
+                    })
+                    .collect();
+                let confidence = (!tokens.is_empty())
+                    .then(|| tokens.iter().map(|(_, p)| p).sum::<f32>() / tokens.len() as f32);
+                segments.push(EngineSegment {
+                    // Timestamps are in centiseconds
+                    start: segment.start_timestamp() as f32 / 100.0,
+                    end: segment.end_timestamp() as f32 / 100.0,
+                    confidence,
+                    word_confidences: word_confidences(&tokens),
+                    text: segment_text,
+                });
+            }
+        }
+
+        Ok(EngineTranscription {
+            text: text.trim().to_string(),
+            segments,
+            language,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_most_likely_language() {
+        let mut probabilities = vec![0.0; whisper_rs::get_lang_max_id() as usize + 1];
+        probabilities[whisper_rs::get_lang_id("en").unwrap() as usize] = 0.6;
+        probabilities[whisper_rs::get_lang_id("cy").unwrap() as usize] = 0.3;
+        probabilities[whisper_rs::get_lang_id("de").unwrap() as usize] = 0.1;
+
+        let allowed = vec!["de".to_string(), "cy".to_string()];
+        assert_eq!(
+            most_likely_language(&probabilities, &allowed),
+            Some(DetectedLanguage {
+                code: "cy".to_string(),
+                probability: Some(0.3),
+            })
+        );
+        let any = most_likely_language(&probabilities, &[]).unwrap();
+        assert_eq!(any.code, "en");
+    }
+
+    #[test]
+    fn test_word_confidences_group_tokens_by_word() {
+        let tokens: Vec<(String, f32)> = [
+            (" Kub", 0.4),
+            ("ern", 0.2),
+            ("etes", 0.6),
+            (",", 0.9),
+            (" ", 0.5),
+            ("rocks", 0.8),
+        ]
+        .into_iter()
+        .map(|(text, p)| (text.to_string(), p))
+        .collect();
+
+        let confidences = word_confidences(&tokens);
+        assert_eq!(
+            confidences.len(),
+            " Kubernetes, rocks".split_whitespace().count()
+        );
+        assert!((confidences[0] - 0.525).abs() < 0.001);
+        assert_eq!(confidences[1], 0.8);
+        assert!(word_confidences(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_sampling_strategy_follows_decoding_params() {
+        let strategy = sampling_strategy(&DecodingParams::default());
+        assert!(matches!(
+            strategy,
+            SamplingStrategy::BeamSearch { beam_size: 3, .. }
+        ));
+
+        let params = DecodingParams {
+            beam_size: Some(5),
+            best_of: Some(2),
+            ..Default::default()
+        };
+        assert!(matches!(
+            sampling_strategy(&params),
+            SamplingStrategy::BeamSearch { beam_size: 5, .. }
+        ));
+
+        let params = DecodingParams {
+            beam_size: Some(1),
+            ..Default::default()
+        };
+        assert!(matches!(
+            sampling_strategy(&params),
+            SamplingStrategy::Greedy { best_of: 5 }
+        ));
+
+        let params = DecodingParams {
+            best_of: Some(3),
+            ..Default::default()
+        };
+        assert!(matches!(
+            sampling_strategy(&params),
+            SamplingStrategy::Greedy { best_of: 3 }
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+use std::fmt;
+
+/// Rewrites may legitimately grow (formatting, bullet points), but not unboundedly
+const MAX_LENGTH_RATIO: f64 = 3.0;
+/// Extra words always allowed on top of the ratio so short dictations can be expanded a bit
+const LENGTH_SLACK_WORDS: usize = 30;
+/// Below this share of the original's length the rewrite was probably cut off
+const MIN_LENGTH_RATIO: f64 = 0.2;
+/// Share of the original's content words that must survive the rewrite
+const MIN_OVERLAP: f64 = 0.15;
+/// Overlap and truncation checks are meaningless for very short dictations
+const MIN_WORDS_FOR_RATIO_CHECKS: usize = 8;
+
+const LEAKED_TAGS: &[&str] = &[
+    "<transcription>",
+    "</transcription>",
+    "<rewriting_instructions>",
+    "</rewriting_instructions>",
+    "<reply_context>",
+    "</reply_context>",
+];
+
+/// Openers that mean the model answered or refused instead of rewriting
+const ASSISTANT_OPENERS: &[&str] = &[
+    "i'm sorry",
+    "i am sorry",
+    "i apologize",
+    "i cannot",
+    "i can't",
+    "i can not",
+    "i'm unable",
+    "i am unable",
+    "as an ai",
+    "as a language model",
+    "sure, here",
+    "sure! here",
+    "certainly!",
+    "certainly, here",
+    "of course! here",
+    "i'd be happy to",
+    "i would be happy to",
+    "could you please provide",
+    "could you clarify",
+    "it seems like you",
+    "it looks like you",
+];
+
+/// Common words ignored by the overlap check
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "but", "not", "you", "all", "any", "can", "her", "was", "one",
+    "our", "out", "has", "have", "had", "his", "how", "its", "may", "who", "did", "get", "him",
+    "let", "say", "she", "too", "use", "that", "this", "with", "from", "they", "will", "would",
+    "there", "their", "what", "about", "which", "when", "your", "just", "like", "also", "been",
+    "into", "than", "then", "them", "were", "some", "could", "should",
+];
+
+/// Why a rewrite was thrown away in favour of the original transcription. Returned
+/// through anyhow so callers can tell it apart from provider failures.
+#[derive(Debug)]
+pub struct RewriteRejected {
+    /// Human-readable reason, e.g. "the rewrite is 4.2x longer than the dictation"
+    pub reason: String,
+}
+
+impl fmt::Display for RewriteRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rewrite rejected: {}", self.reason)
+    }
+}
+
+impl std::error::Error for RewriteRejected {}
+
+/// Checks a ghostwriter rewrite against the dictated text it was produced from.
+///
+/// Returns the reason when the rewrite looks like an answer, a refusal,
+/// a runaway generation or leaked prompt scaffolding rather than a rewrite. The word
+/// overlap check is skipped when `check_overlap` is false, for rewrites that
+/// legitimately share few words with the dictation (replies, translations).
+pub fn check_rewrite(
+    original: &str,
+    rewritten: &str,
+    check_overlap: bool,
+) -> Result<(), RewriteRejected> {
+    let rewritten_lower = rewritten.to_lowercase();
+    let original_lower = original.to_lowercase();
+
+    if rewritten.trim().is_empty() {
+        return Err(rejected("the model returned an empty rewrite".to_string()));
+    }
+
+    if let Some(tag) = LEAKED_TAGS
+        .iter()
+        .find(|tag| rewritten_lower.contains(*tag))
+    {
+        return Err(rejected(format!(
+            "the rewrite contains prompt markup ({})",
+            tag
+        )));
+    }
+
+    // Ignore openers the speaker actually dictated ("I'm sorry I missed the call...")
+    if let Some(opener) = ASSISTANT_OPENERS
+        .iter()
+        .find(|o| rewritten_lower.trim_start().starts_with(*o) && !original_lower.contains(*o))
+    {
+        return Err(rejected(format!(
+            "the model replied instead of rewriting (\"{}...\")",
+            opener
+        )));
+    }
+
+    let original_words = original.split_whitespace().count();
+    let rewritten_words = rewritten.split_whitespace().count();
+
+    let max_words = ((original_words as f64 * MAX_LENGTH_RATIO) as usize)
+        .max(original_words + LENGTH_SLACK_WORDS);
+    if rewritten_words > max_words {
+        return Err(rejected(format!(
+            "the rewrite is {:.1}x longer than the dictation",
+            rewritten_words as f64 / original_words.max(1) as f64
+        )));
+    }
+
+    if original_words < MIN_WORDS_FOR_RATIO_CHECKS {
+        return Ok(());
+    }
+
+    if (rewritten_words as f64) < original_words as f64 * MIN_LENGTH_RATIO {
+        return Err(rejected(format!(
+            "the rewrite kept only {} of {} words",
+            rewritten_words, original_words
+        )));
+    }
+
+    let original_terms = content_words(&original_lower);
+    if check_overlap && original_terms.len() >= MIN_WORDS_FOR_RATIO_CHECKS {
+        let rewritten_terms = content_words(&rewritten_lower);
+        let shared = original_terms.intersection(&rewritten_terms).count();
+        let overlap = shared as f64 / original_terms.len() as f64;
+        if overlap < MIN_OVERLAP {
+            return Err(rejected(format!(
+                "the rewrite shares only {:.0}% of the dictated words",
+                overlap * 100.0
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn rejected(reason: String) -> RewriteRejected {
+    RewriteRejected { reason }
+}
+
+fn content_words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICTATION: &str = "so um tell the team that the quarterly report is delayed until friday because finance still needs to sign off on the budget numbers";
+
+    #[test]
+    fn test_accepts_normal_rewrite() {
+        let rewrite = "Please let the team know the quarterly report is delayed until Friday, as finance still needs to sign off on the budget numbers.";
+        assert!(check_rewrite(DICTATION, rewrite, true).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_answer_to_question() {
+        let dictation = "what is the best way to structure a rust workspace with several crates and shared dependencies";
+        let answer = "Organize the project with a root Cargo manifest listing members, keep common versions pinned in one place, and split binaries from libraries so each compiles independently while CI caches artifacts.";
+        let err = check_rewrite(dictation, answer, true).unwrap_err();
+        assert!(err.reason.contains("shares only"), "{}", err);
+    }
+
+    #[test]
+    fn test_skips_overlap_for_translations() {
+        let translation = "Bitte sag dem Team, dass sich der Quartalsbericht bis Freitag verzögert, weil die Finanzabteilung die Budgetzahlen noch freigeben muss.";
+        assert!(check_rewrite(DICTATION, translation, true).is_err());
+        assert!(check_rewrite(DICTATION, translation, false).is_ok());
+
+        // The other checks still apply
+        let rewrite = "Das Team wartet. ".repeat(40);
+        assert!(check_rewrite(DICTATION, &rewrite, false).is_err());
+        assert!(check_rewrite(DICTATION, "I'm sorry, but I can't help with that.", false).is_err());
+    }
+
+    #[test]
+    fn test_rejects_runaway_length() {
+        let rewrite = "The quarterly report is delayed. ".repeat(40);
+        let err = check_rewrite(DICTATION, &rewrite, true).unwrap_err();
+        assert!(err.reason.contains("longer"), "{}", err);
+    }
+
+    #[test]
+    fn test_rejects_refusal_and_leaked_tags() {
+        assert!(check_rewrite(DICTATION, "I'm sorry, but I can't help with that.", true).is_err());
+        assert!(check_rewrite(
+            DICTATION,
+            "<transcription>The report is delayed.</transcription>",
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_allows_dictated_apology() {
+        let dictation =
+            "i'm sorry i missed the call yesterday can we move it to thursday afternoon instead";
+        let rewrite =
+            "I'm sorry I missed the call yesterday. Can we move it to Thursday afternoon instead?";
+        assert!(check_rewrite(dictation, rewrite, true).is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+use super::GhostwriterProvider;
+use anyhow::Result;
+
+/// Any server that exposes the OpenAI chat completions API, e.g. Ollama,
+/// llama.cpp's `llama-server` or LM Studio. Lets dictation stay on the local machine.
+pub struct OpenAiCompatibleProvider {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl OpenAiCompatibleProvider {
+    /// `base_url` is the API root including the version segment, e.g. `http://localhost:11434/v1`
+    pub fn new(base_url: &str, api_key: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            api_key,
+        }
+    }
+}
+
+impl GhostwriterProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &str {
+        "OpenAI-compatible server"
+    }
+
+    fn chat_completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    fn models_url(&self) -> String {
+        format!("{}/models", self.base_url)
+    }
+
+    fn check_configured(&self) -> Result<()> {
+        if self.base_url.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No server URL configured. Please add your OpenAI-compatible server URL in settings."
+            ));
+        }
+        Ok(())
+    }
+
+    fn apply_headers(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        // Local servers usually run without auth, so the header is optional
+        match &self.api_key {
+            Some(key) if !key.is_empty() => {
+                request.header("Authorization", format!("Bearer {}", key))
+            }
+            _ => request,
+        }
+    }
+
+    fn parse_error(&self, status: u16, error_text: &str, model: &str) -> String {
+        // OpenAI style: {"error":{"message":"..."}}, Ollama style: {"error":"..."}
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(error_text) {
+            let message = json
+                .pointer("/error/message")
+                .or_else(|| json.get("error").filter(|v| v.is_string()))
+                .or_else(|| json.get("message"))
+                .and_then(|v| v.as_str());
+
+            if let Some(msg) = message {
+                return format!("{}: {}", self.base_url, msg);
+            }
+        }
+
+        match status {
+            401 | 403 => format!(
+                "{} rejected the request. Check the API key in Settings.",
+                self.base_url
+            ),
+            404 => format!("Model '{}' not found on {}.", model, self.base_url),
+            429 => format!(
+                "{} is rate limiting requests. Try again shortly.",
+                self.base_url
+            ),
+            500..=599 => format!(
+                "{} returned a server error ({}). Is the model loaded?",
+                self.base_url, status
+            ),
+            _ => format!("{} returned an error ({}).", self.base_url, status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urls_strip_trailing_slash() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:1234/v1/", None);
+        assert_eq!(
+            provider.chat_completions_url(),
+            "http://localhost:1234/v1/chat/completions"
+        );
+        assert_eq!(provider.models_url(), "http://localhost:1234/v1/models");
+    }
+
+    #[test]
+    fn test_api_key_is_optional() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:11434/v1", None);
+        assert!(provider.check_configured().is_ok());
+    }
+
+    #[test]
+    fn test_parse_ollama_error() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:11434/v1", None);
+        assert_eq!(
+            provider.parse_error(404, r#"{"error":"model \"llama9\" not found"}"#, "llama9"),
+            "http://localhost:11434/v1: model \"llama9\" not found"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use super::GhostwriterProvider;
+use anyhow::Result;
+
+const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+/// Hosted OpenRouter API (default provider)
+pub struct OpenRouterProvider {
+    api_key: Option<String>,
+}
+
+impl OpenRouterProvider {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self { api_key }
+    }
+}
+
+impl GhostwriterProvider for OpenRouterProvider {
+    fn name(&self) -> &str {
+        "OpenRouter"
+    }
+
+    fn chat_completions_url(&self) -> String {
+        format!("{}/chat/completions", OPENROUTER_BASE_URL)
+    }
+
+    fn models_url(&self) -> String {
+        format!("{}/models", OPENROUTER_BASE_URL)
+    }
+
+    fn check_configured(&self) -> Result<()> {
+        match &self.api_key {
+            Some(key) if !key.is_empty() => Ok(()),
+            _ => Err(anyhow::anyhow!(
+                "No API key configured. Please add your OpenRouter API key in settings."
+            )),
+        }
+    }
+
+    fn apply_headers(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let request = request
+            .header("HTTP-Referer", "https://leadrscribe.vercel.app")
+            .header("X-Title", "LeadrScribe");
+
+        match &self.api_key {
+            Some(key) if !key.is_empty() => {
+                request.header("Authorization", format!("Bearer {}", key))
+            }
+            _ => request,
+        }
+    }
+
+    fn parse_error(&self, status: u16, error_text: &str, model: &str) -> String {
+        parse_openrouter_error(status, error_text, model)
+    }
+
+    fn reports_cost(&self) -> bool {
+        true
+    }
+}
+
+/// Parse OpenRouter error JSON into a human-readable message.
+/// OpenRouter format: {"error":{"message":"...","code":400,"metadata":{"raw":"<json-string>","provider_name":"..."}}}
+fn parse_openrouter_error(status: u16, error_text: &str, model: &str) -> String {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(error_text) {
+        if let Some(error_obj) = json.get("error") {
+            let provider = error_obj
+                .pointer("/metadata/provider_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+
+            // Try to extract inner message from metadata.raw (can be a JSON string or object)
+            let inner_msg = error_obj.pointer("/metadata/raw").and_then(|raw_value| {
+                // If raw is a string containing JSON, parse it
+                let raw_json = if let Some(raw_str) = raw_value.as_str() {
+                    serde_json::from_str::<serde_json::Value>(raw_str).ok()
+                } else if raw_value.is_object() {
+                    Some(raw_value.clone())
+                } else {
+                    None
+                };
+
+                raw_json.and_then(|rj| {
+                    // Try common paths: /error/message, /message, /error
+                    rj.pointer("/error/message")
+                        .or_else(|| rj.get("message"))
+                        .or_else(|| rj.get("error").filter(|v| v.is_string()))
+                        .and_then(|v| v.as_str().map(String::from))
+                })
+            });
+
+            if let Some(msg) = inner_msg {
+                return if provider.is_empty() {
+                    msg
+                } else {
+                    format!("{}: {}", provider, msg)
+                };
+            }
+
+            // Fall back to top-level error.message (skip if it's just "Provider returned error")
+            if let Some(msg) = error_obj.get("message").and_then(|v| v.as_str()) {
+                if msg != "Provider returned error" {
+                    return if provider.is_empty() {
+                        msg.to_string()
+                    } else {
+                        format!("{}: {}", provider, msg)
+                    };
+                }
+            }
+
+            // If we have a provider but no useful message, say so
+            if !provider.is_empty() {
+                return match status {
+                    401 => format!("{} rejected the API key.", provider),
+                    402 => format!("{}: insufficient credits.", provider),
+                    429 => format!("{}: rate limit exceeded. Try again shortly.", provider),
+                    _ => format!(
+                        "{} returned an error ({}). Try a different model.",
+                        provider, status
+                    ),
+                };
+            }
+        }
+    }
+
+    // Fallback for non-JSON or unparseable errors
+    match status {
+        401 => "Invalid API key. Check your OpenRouter API key in Settings.".to_string(),
+        402 => "Insufficient credits. Add credits at openrouter.ai.".to_string(),
+        403 => "Access forbidden. Check your API key permissions.".to_string(),
+        404 => format!("Model '{}' not found on OpenRouter.", model),
+        429 => "Rate limit exceeded. Wait a moment and try again.".to_string(),
+        500..=599 => "OpenRouter service error. Try again later.".to_string(),
+        _ => format!("OpenRouter error ({}). Try a different model.", status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_prefers_raw_provider_message() {
+        let body = r#"{"error":{"message":"Provider returned error","code":400,"metadata":{"raw":"{\"error\":{\"message\":\"context too long\"}}","provider_name":"Anthropic"}}}"#;
+        assert_eq!(
+            parse_openrouter_error(400, body, "anthropic/claude-3.5-sonnet"),
+            "Anthropic: context too long"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_non_json_fallback() {
+        assert_eq!(
+            parse_openrouter_error(404, "not found", "foo/bar"),
+            "Model 'foo/bar' not found on OpenRouter."
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::time::Duration;
+
+const BASE_BACKOFF: Duration = Duration::from_millis(1000);
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Longest `Retry-After` we are willing to wait while the user is waiting for a paste.
+/// Anything longer skips straight to the next fallback model.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(20);
+
+/// How failed requests are retried and which models are tried after the primary one
+#[derive(Clone, Debug, Default)]
+pub struct RetryPolicy {
+    /// Extra attempts per model for rate limits, server errors and network failures
+    pub max_retries: u32,
+    /// Models tried in order when the primary model keeps failing
+    pub fallback_models: Vec<String>,
+}
+
+impl RetryPolicy {
+    /// Primary model followed by the fallbacks, skipping blanks and duplicates
+    pub fn model_chain(&self, primary: &str) -> Vec<String> {
+        let mut chain = vec![primary.to_string()];
+        for model in &self.fallback_models {
+            let model = model.trim();
+            if !model.is_empty() && !chain.iter().any(|m| m == model) {
+                chain.push(model.to_string());
+            }
+        }
+        chain
+    }
+}
+
+/// Worth retrying the same model (transient failure)
+pub fn is_retryable(status: u16) -> bool {
+    matches!(status, 408 | 429 | 500..=599)
+}
+
+/// Worth moving on to the next model (model missing, overloaded or rate limited)
+pub fn should_fall_back(status: u16) -> bool {
+    matches!(status, 404 | 408 | 429 | 500..=599)
+}
+
+/// A chat request that produced no answer, classified so the retry loop can tell
+/// transient failures from ones that would fail the same way again
+#[derive(Debug)]
+pub enum ChatError {
+    /// The provider answered with an error status, up front or inside the stream
+    Status {
+        status: u16,
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// The connection failed or the response was cut off
+    Network(String),
+    /// A response arrived but couldn't be used
+    Invalid(String),
+}
+
+impl ChatError {
+    /// Worth retrying the same model
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ChatError::Status { status, .. } => is_retryable(*status),
+            ChatError::Network(_) => true,
+            ChatError::Invalid(_) => false,
+        }
+    }
+
+    /// Worth moving on to the next model
+    pub fn should_fall_back(&self) -> bool {
+        match self {
+            ChatError::Status { status, .. } => should_fall_back(*status),
+            ChatError::Network(_) => true,
+            ChatError::Invalid(_) => false,
+        }
+    }
+
+    /// How long the provider asked us to wait before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ChatError::Status { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatError::Status { message, .. }
+            | ChatError::Network(message)
+            | ChatError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+/// Parses a `Retry-After` header given either as delta-seconds or as an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(Duration::from_secs(wait.max(0) as u64))
+}
+
+/// Delay before retry number `attempt` (0-based). Returns None when the server asked us
+/// to wait longer than we're willing to, meaning the caller should stop retrying this model.
+pub fn backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+    match retry_after {
+        Some(wait) if wait > MAX_RETRY_AFTER => None,
+        Some(wait) => Some(wait),
+        None => Some(
+            BASE_BACKOFF
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(MAX_BACKOFF),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        assert_eq!(backoff_delay(0, None), Some(Duration::from_secs(1)));
+        assert_eq!(backoff_delay(2, None), Some(Duration::from_secs(4)));
+        assert_eq!(backoff_delay(10, None), Some(MAX_BACKOFF));
+    }
+
+    #[test]
+    fn test_retry_after_is_honoured() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(
+            backoff_delay(0, Some(Duration::from_secs(3))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(backoff_delay(0, Some(Duration::from_secs(600))), None);
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_chat_errors_fail_fast_unless_transient() {
+        let status = |status| ChatError::Status {
+            status,
+            retry_after: None,
+            message: String::new(),
+        };
+        assert!(status(429).is_retryable() && status(429).should_fall_back());
+        assert!(!status(404).is_retryable() && status(404).should_fall_back());
+        for status in [status(400), status(401)] {
+            assert!(!status.is_retryable() && !status.should_fall_back());
+        }
+        let network = ChatError::Network("Stream interrupted".to_string());
+        assert!(network.is_retryable() && network.should_fall_back());
+        let invalid = ChatError::Invalid("Failed to parse API response".to_string());
+        assert!(!invalid.is_retryable() && !invalid.should_fall_back());
+    }
+
+    #[test]
+    fn test_model_chain_dedupes() {
+        let policy = RetryPolicy {
+            max_retries: 0,
+            fallback_models: vec![
+                "a/primary".to_string(),
+                " ".to_string(),
+                "b/fallback".to_string(),
+            ],
+        };
+        assert_eq!(
+            policy.model_chain("a/primary"),
+            vec!["a/primary", "b/fallback"]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use serde::Deserialize;
+
+use super::Usage;
+
+/// One decoded event from a chat completions SSE stream
+#[derive(Debug, PartialEq)]
+pub enum StreamEvent {
+    /// Incremental text; either field may be empty
+    Delta { content: String, reasoning: String },
+    /// Provider reported an error after the stream had started (HTTP status was already 200)
+    Error { status: u16, body: String },
+    /// Token usage, sent in the final chunk when requested
+    Usage(Usage),
+    /// `data: [DONE]`
+    Done,
+}
+
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    #[serde(default)]
+    delta: Option<StreamDelta>,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    reasoning: Option<String>,
+}
+
+/// Incremental parser for `text/event-stream` bodies.
+///
+/// Network chunks can split lines (and UTF-8 sequences) anywhere, so bytes are buffered
+/// until a full line is available. Only `data:` fields are used; comments such as
+/// OpenRouter's `: OPENROUTER PROCESSING` keep-alives are ignored.
+#[derive(Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds raw bytes and returns every event completed by them
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            events.extend(parse_line(line.trim_end_matches(['\r', '\n'])));
+        }
+        events
+    }
+
+    /// Flushes a trailing line that wasn't newline-terminated
+    pub fn finish(&mut self) -> Vec<StreamEvent> {
+        if self.buffer.is_empty() {
+            return Vec::new();
+        }
+        let line = String::from_utf8_lossy(&self.buffer).into_owned();
+        self.buffer.clear();
+        parse_line(line.trim_end_matches(['\r', '\n']))
+    }
+}
+
+fn parse_line(line: &str) -> Vec<StreamEvent> {
+    let Some(data) = line.strip_prefix("data:").map(str::trim_start) else {
+        return Vec::new();
+    };
+    if data.is_empty() {
+        return Vec::new();
+    }
+    if data == "[DONE]" {
+        return vec![StreamEvent::Done];
+    }
+
+    let json: serde_json::Value = match serde_json::from_str(data) {
+        Ok(json) => json,
+        Err(_) => return Vec::new(),
+    };
+
+    // OpenRouter/OpenAI report mid-stream failures as {"error":{"message":..,"code":..}}
+    if let Some(error) = json.get("error") {
+        let status = error
+            .get("code")
+            .and_then(|c| c.as_u64())
+            .and_then(|c| u16::try_from(c).ok())
+            .unwrap_or(500);
+        return vec![StreamEvent::Error {
+            status,
+            body: data.to_string(),
+        }];
+    }
+
+    let Ok(chunk) = serde_json::from_value::<StreamChunk>(json) else {
+        return Vec::new();
+    };
+
+    // The usage chunk may carry a final (usually empty) delta as well
+    let mut events = Vec::new();
+    if let Some(delta) = chunk.choices.into_iter().next().and_then(|c| c.delta) {
+        events.push(StreamEvent::Delta {
+            content: delta.content.unwrap_or_default(),
+            reasoning: delta.reasoning.unwrap_or_default(),
+        });
+    }
+    if let Some(usage) = chunk.usage {
+        events.push(StreamEvent::Usage(usage));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(text: &str) -> StreamEvent {
+        StreamEvent::Delta {
+            content: text.to_string(),
+            reasoning: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parses_deltas_split_across_chunks() {
+        let mut parser = SseParser::new();
+        assert!(parser
+            .push(b"data: {\"choices\":[{\"delta\":{\"content\":\"Hel")
+            .is_empty());
+        assert_eq!(
+            parser
+                .push(b"lo\"}}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\" world\"}}]}\n"),
+            vec![content("Hello"), content(" world")]
+        );
+        assert_eq!(parser.push(b"data: [DONE]\n\n"), vec![StreamEvent::Done]);
+    }
+
+    #[test]
+    fn test_ignores_comments_and_crlf() {
+        let mut parser = SseParser::new();
+        let events = parser.push(b": OPENROUTER PROCESSING\r\n\r\ndata: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\r\n");
+        assert_eq!(events, vec![content("Hi")]);
+    }
+
+    #[test]
+    fn test_mid_stream_error() {
+        let mut parser = SseParser::new();
+        let events = parser.push(b"data: {\"error\":{\"message\":\"overloaded\",\"code\":502}}\n");
+        assert_eq!(
+            events,
+            vec![StreamEvent::Error {
+                status: 502,
+                body: "{\"error\":{\"message\":\"overloaded\",\"code\":502}}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_usage_chunk() {
+        let mut parser = SseParser::new();
+        let events = parser.push(
+            b"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":120,\"completion_tokens\":45,\"cost\":0.0012}}\n",
+        );
+        assert_eq!(
+            events,
+            vec![StreamEvent::Usage(Usage {
+                prompt_tokens: 120,
+                completion_tokens: 45,
+                cost: Some(0.0012),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_reasoning_delta_and_unterminated_tail() {
+        let mut parser = SseParser::new();
+        assert!(parser
+            .push(b"data: {\"choices\":[{\"delta\":{\"reasoning\":\"thinking\"}}]}")
+            .is_empty());
+        assert_eq!(
+            parser.finish(),
+            vec![StreamEvent::Delta {
+                content: String::new(),
+                reasoning: "thinking".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use chrono::{DateTime, Local};
+
+/// Variables that may appear as `{name}` in global or profile custom instructions
+pub const VARIABLES: &[&str] = &[
+    "date",
+    "time",
+    "weekday",
+    "language",
+    "profile_name",
+    "clipboard",
+    "user_name",
+];
+
+/// Values substituted into custom instructions at ghostwriting time
+#[derive(Clone, Debug)]
+pub struct TemplateContext {
+    pub now: DateTime<Local>,
+    /// Transcription language code, or "auto"
+    pub language: String,
+    pub profile_name: Option<String>,
+    pub clipboard: Option<String>,
+    pub user_name: String,
+}
+
+impl TemplateContext {
+    fn value(&self, variable: &str) -> Option<String> {
+        let value = match variable {
+            "date" => self.now.format("%Y-%m-%d").to_string(),
+            "time" => self.now.format("%H:%M").to_string(),
+            "weekday" => self.now.format("%A").to_string(),
+            "language" if self.language == "auto" => "the dictated language".to_string(),
+            "language" => self.language.clone(),
+            "profile_name" => self.profile_name.clone().unwrap_or_default(),
+            "clipboard" => self
+                .clipboard
+                .as_deref()
+                .map(|c| super::truncate_chars(c, super::MAX_REPLY_CONTEXT_CHARS).to_string())
+                .unwrap_or_default(),
+            "user_name" => self.user_name.clone(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// A piece of a parsed template
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+    /// `{` and an identifier without the closing brace, e.g. `{date is`
+    Unclosed(&'a str),
+}
+
+/// Splits a template into literal text and `{identifier}` placeholders.
+///
+/// `{{` and `}}` are escapes for literal braces. Braces that don't enclose a plain
+/// identifier (e.g. JSON examples in the instructions) are kept as text.
+fn tokenize(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        if pos > 0 {
+            tokens.push(Token::Text(&rest[..pos]));
+        }
+        rest = &rest[pos..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            tokens.push(Token::Text(&rest[..1]));
+            rest = &rest[2..];
+            continue;
+        }
+
+        if rest.starts_with('{') {
+            let name_len = rest[1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len() - 1);
+            if name_len > 0 {
+                let name = &rest[1..1 + name_len];
+                if rest[1 + name_len..].starts_with('}') {
+                    tokens.push(Token::Variable(name));
+                    rest = &rest[name_len + 2..];
+                } else {
+                    tokens.push(Token::Unclosed(&rest[..1 + name_len]));
+                    rest = &rest[1 + name_len..];
+                }
+                continue;
+            }
+        }
+
+        tokens.push(Token::Text(&rest[..1]));
+        rest = &rest[1..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// Whether the template references `variable`, so expensive values (clipboard) are only
+/// read when needed
+pub fn uses_variable(template: &str, variable: &str) -> bool {
+    tokenize(template).contains(&Token::Variable(variable))
+}
+
+/// Rejects templates that reference unknown variables or leave a variable unclosed
+pub fn validate(template: &str) -> Result<(), String> {
+    let tokens = tokenize(template);
+    let unknown: Vec<String> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Variable(name) if !VARIABLES.contains(name) => Some(format!("{{{}}}", name)),
+            _ => None,
+        })
+        .collect();
+    let unclosed: Vec<&str> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Unclosed(text) => Some(*text),
+            _ => None,
+        })
+        .collect();
+
+    if !unclosed.is_empty() {
+        return Err(format!(
+            "Unclosed template variable{} {}. Close {} with }} or use {{{{ for a literal brace.",
+            if unclosed.len() > 1 { "s" } else { "" },
+            unclosed.join(", "),
+            if unclosed.len() > 1 { "them" } else { "it" }
+        ));
+    }
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let available: Vec<String> = VARIABLES.iter().map(|v| format!("{{{}}}", v)).collect();
+    Err(format!(
+        "Unknown template variable{} {}. Available variables: {}. Use {{{{ and }}}} for literal braces.",
+        if unknown.len() > 1 { "s" } else { "" },
+        unknown.join(", "),
+        available.join(", ")
+    ))
+}
+
+/// Substitutes variables from `context`. Unknown variables are left as written.
+pub fn render(template: &str, context: &TemplateContext) -> String {
+    let mut output = String::with_capacity(template.len());
+    for token in tokenize(template) {
+        match token {
+            Token::Text(text) | Token::Unclosed(text) => output.push_str(text),
+            Token::Variable(name) => match context.value(name) {
+                Some(value) => output.push_str(&value),
+                None => {
+                    output.push('{');
+                    output.push_str(name);
+                    output.push('}');
+                }
+            },
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            now: Local.with_ymd_and_hms(2024, 3, 15, 9, 5, 0).unwrap(),
+            language: "de".to_string(),
+            profile_name: Some("Sales".to_string()),
+            clipboard: Some("Hi, can we meet?".to_string()),
+            user_name: "Sam".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        let rendered = render(
+            "Sign as {user_name} ({profile_name}) on {weekday} {date} at {time}, in {language}. Context: {clipboard}",
+            &context(),
+        );
+        assert_eq!(
+            rendered,
+            "Sign as Sam (Sales) on Friday 2024-03-15 at 09:05, in de. Context: Hi, can we meet?"
+        );
+    }
+
+    #[test]
+    fn test_escapes_and_non_variable_braces() {
+        let template = r#"Output {{user_name}} literally and JSON like {"a": 1} as-is"#;
+        assert_eq!(
+            render(template, &context()),
+            r#"Output {user_name} literally and JSON like {"a": 1} as-is"#
+        );
+        assert!(validate(template).is_ok());
+        assert!(!uses_variable(template, "user_name"));
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_variables() {
+        assert!(validate("Hello {user_name}, today is {date}").is_ok());
+        let err = validate("Hello {username} from {company}").unwrap_err();
+        assert!(err.contains("{username}, {company}"), "{}", err);
+        assert!(err.contains("{user_name}"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_reports_unclosed_variables() {
+        let err = validate("Today is {date and I am {user_name").unwrap_err();
+        assert!(err.contains("{date, {user_name"), "{}", err);
+        assert!(validate("Code like fn main() { ok }").is_ok());
+        assert_eq!(render("Today is {date", &context()), "Today is {date");
+    }
+
+    #[test]
+    fn test_auto_language_and_missing_values() {
+        let context = TemplateContext {
+            language: "auto".to_string(),
+            profile_name: None,
+            clipboard: None,
+            ..context()
+        };
+        assert_eq!(
+            render(
+                "[{language}] [{profile_name}] [{clipboard}] {unknown}",
+                &context
+            ),
+            "[the dictated language] [] [] {unknown}"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use whatlang::{Detector, Lang};
+
+/// Whisper language codes, their English names and the matching whatlang language.
+/// Whisper picks the spoken language itself; whatlang identifies the language of the
+/// finished transcript, so languages it doesn't know are never reported as detected.
+const LANGUAGES: &[(&str, &str, Option<Lang>)] = &[
+    ("af", "Afrikaans", Some(Lang::Afr)),
+    ("am", "Amharic", Some(Lang::Amh)),
+    ("ar", "Arabic", Some(Lang::Ara)),
+    ("az", "Azerbaijani", Some(Lang::Aze)),
+    ("be", "Belarusian", Some(Lang::Bel)),
+    ("bg", "Bulgarian", Some(Lang::Bul)),
+    ("bn", "Bengali", Some(Lang::Ben)),
+    ("br", "Breton", None),
+    ("ca", "Catalan", Some(Lang::Cat)),
+    ("cs", "Czech", Some(Lang::Ces)),
+    ("cy", "Welsh", None),
+    ("da", "Danish", Some(Lang::Dan)),
+    ("de", "German", Some(Lang::Deu)),
+    ("el", "Greek", Some(Lang::Ell)),
+    ("en", "English", Some(Lang::Eng)),
+    ("es", "Spanish", Some(Lang::Spa)),
+    ("et", "Estonian", Some(Lang::Est)),
+    ("fa", "Persian", Some(Lang::Pes)),
+    ("fi", "Finnish", Some(Lang::Fin)),
+    ("fr", "French", Some(Lang::Fra)),
+    ("gu", "Gujarati", Some(Lang::Guj)),
+    ("he", "Hebrew", Some(Lang::Heb)),
+    ("hi", "Hindi", Some(Lang::Hin)),
+    ("hr", "Croatian", Some(Lang::Hrv)),
+    ("hu", "Hungarian", Some(Lang::Hun)),
+    ("hy", "Armenian", Some(Lang::Hye)),
+    ("id", "Indonesian", Some(Lang::Ind)),
+    ("it", "Italian", Some(Lang::Ita)),
+    ("ja", "Japanese", Some(Lang::Jpn)),
+    ("jw", "Javanese", Some(Lang::Jav)),
+    ("ka", "Georgian", Some(Lang::Kat)),
+    ("km", "Khmer", Some(Lang::Khm)),
+    ("kn", "Kannada", Some(Lang::Kan)),
+    ("ko", "Korean", Some(Lang::Kor)),
+    ("la", "Latin", Some(Lang::Lat)),
+    ("lt", "Lithuanian", Some(Lang::Lit)),
+    ("lv", "Latvian", Some(Lang::Lav)),
+    ("mi", "Maori", None),
+    ("mk", "Macedonian", Some(Lang::Mkd)),
+    ("ml", "Malayalam", Some(Lang::Mal)),
+    ("mr", "Marathi", Some(Lang::Mar)),
+    ("ms", "Malay", None),
+    ("my", "Burmese", Some(Lang::Mya)),
+    ("ne", "Nepali", Some(Lang::Nep)),
+    ("nl", "Dutch", Some(Lang::Nld)),
+    ("no", "Norwegian", Some(Lang::Nob)),
+    ("pa", "Punjabi", Some(Lang::Pan)),
+    ("pl", "Polish", Some(Lang::Pol)),
+    ("pt", "Portuguese", Some(Lang::Por)),
+    ("ro", "Romanian", Some(Lang::Ron)),
+    ("ru", "Russian", Some(Lang::Rus)),
+    ("si", "Sinhala", Some(Lang::Sin)),
+    ("sk", "Slovak", Some(Lang::Slk)),
+    ("sl", "Slovenian", Some(Lang::Slv)),
+    ("sn", "Shona", Some(Lang::Sna)),
+    ("sr", "Serbian", Some(Lang::Srp)),
+    ("sv", "Swedish", Some(Lang::Swe)),
+    ("ta", "Tamil", Some(Lang::Tam)),
+    ("te", "Telugu", Some(Lang::Tel)),
+    ("th", "Thai", Some(Lang::Tha)),
+    ("tk", "Turkmen", Some(Lang::Tuk)),
+    ("tl", "Tagalog", Some(Lang::Tgl)),
+    ("tr", "Turkish", Some(Lang::Tur)),
+    ("uk", "Ukrainian", Some(Lang::Ukr)),
+    ("ur", "Urdu", Some(Lang::Urd)),
+    ("uz", "Uzbek", Some(Lang::Uzb)),
+    ("vi", "Vietnamese", Some(Lang::Vie)),
+    ("yi", "Yiddish", Some(Lang::Yid)),
+    ("zh", "Chinese", Some(Lang::Cmn)),
+];
+
+/// The language a transcript is in
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DetectedLanguage {
+    /// Whisper language code, e.g. "en"
+    pub code: String,
+    /// Detection confidence from 0 to 1; None when the language was set explicitly
+    pub probability: Option<f32>,
+}
+
+fn to_whatlang(code: &str) -> Option<Lang> {
+    LANGUAGES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .and_then(|(_, _, lang)| *lang)
+}
+
+fn to_whisper(lang: Lang) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(_, _, l)| *l == Some(lang))
+        .map(|(code, _, _)| *code)
+}
+
+/// English name of a Whisper language code, for prompts
+pub fn language_name(code: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, name, _)| *name)
+}
+
+/// Identifies the language of `text`. A non-empty `allowed` list restricts the
+/// candidates to those Whisper language codes.
+pub fn detect_language(text: &str, allowed: &[String]) -> Option<DetectedLanguage> {
+    let detector = if allowed.is_empty() {
+        Detector::new()
+    } else {
+        let langs: Vec<Lang> = allowed
+            .iter()
+            .filter_map(|code| to_whatlang(code))
+            .collect();
+        if langs.is_empty() {
+            return None;
+        }
+        Detector::with_allowlist(langs)
+    };
+
+    let info = detector.detect(text)?;
+    Some(DetectedLanguage {
+        code: to_whisper(info.lang())?.to_string(),
+        probability: Some(info.confidence() as f32),
+    })
+}
+
+/// Whether `text` is reliably in a language other than the Whisper language `code`
+pub fn is_other_language(text: &str, code: &str) -> bool {
+    Detector::new()
+        .detect(text)
+        .filter(|info| info.is_reliable())
+        .is_some_and(|info| to_whisper(info.lang()) != Some(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_with_allowlist() {
+        let text = "Der schnelle braune Fuchs springt über den faulen Hund und läuft in den Wald.";
+        assert_eq!(detect_language(text, &[]).unwrap().code, "de");
+
+        let allowed = vec!["en".to_string(), "nl".to_string()];
+        let restricted = detect_language(text, &allowed).unwrap();
+        assert!(allowed.contains(&restricted.code));
+        assert!(detect_language(text, &["cy".to_string()]).is_none());
+        assert_eq!(language_name("cy"), Some("Welsh"));
+    }
+
+    #[test]
+    fn test_is_other_language() {
+        let german =
+            "Der schnelle braune Fuchs springt über den faulen Hund und läuft in den Wald.";
+        assert!(is_other_language(german, "en"));
+        assert!(!is_other_language(german, "de"));
+        assert!(!is_other_language("ok", "de"));
+    }
+}
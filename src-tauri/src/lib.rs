@@ -4,18 +4,24 @@ pub mod audio_toolkit;
 mod clipboard;
 mod commands;
 mod cpu_features;
+mod engines;
 mod ghostwriter;
+mod language;
+mod long_form;
 mod managers;
 mod migration;
 mod overlay;
 mod settings;
 mod shortcut;
+mod stabilizer;
+mod subtitles;
 mod tray;
 mod utils;
 
 use log::{debug, error, info, warn};
 use managers::audio::AudioRecordingManager;
 use managers::history::HistoryManager;
+use managers::import::ImportManager;
 use managers::model::ModelManager;
 use managers::profile::ProfileManager;
 use managers::tag::TagManager;
@@ -84,6 +90,14 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         Arc::new(ProfileManager::new(app_handle).expect("Failed to initialize profile manager"));
     let tag_manager =
         Arc::new(TagManager::new(app_handle).expect("Failed to initialize tag manager"));
+    let import_manager = Arc::new(
+        ImportManager::new(
+            app_handle,
+            transcription_manager.clone(),
+            history_manager.clone(),
+        )
+        .expect("Failed to initialize import manager"),
+    );
 
     // Add managers to Tauri's managed state
     app_handle.manage(recording_manager.clone());
@@ -92,6 +106,7 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(history_manager.clone());
     app_handle.manage(profile_manager.clone());
     app_handle.manage(tag_manager.clone());
+    app_handle.manage(import_manager.clone());
 
     // Initialize the shortcuts
     shortcut::init_shortcuts(app_handle);
@@ -216,14 +231,14 @@ pub fn run() {
                 let model_manager_clone = model_manager.inner().clone();
                 let app_handle_clone = app_handle.clone();
 
-                // Check if selected model is Parakeet and CPU doesn't support it
+                // Check if the selected model's engine can run on this CPU
                 if let Some(model_info) = model_manager.get_model_info(&selected_model) {
-                    if matches!(model_info.engine_type, managers::model::EngineType::Parakeet)
-                        && !cpu_features::supports_parakeet() {
+                    let engine = engines::create_engine(&model_info.engine_type);
+                    if let Err(e) = engine.check_supported() {
                         warn!(
-                            "Selected Parakeet model '{}' requires AVX2 CPU support. \
-                             Your CPU does not support this feature. Switching to Whisper Small model.",
-                            selected_model
+                            "Selected model '{}' can't run on this machine: {} \
+                             Switching to Whisper Small model.",
+                            selected_model, e
                         );
 
                         // Switch to small as a safe default
@@ -238,7 +253,7 @@ pub fn run() {
                         let _ = app_handle_clone.emit(
                             "cpu-incompatible-model",
                             serde_json::json!({
-                                "message": "Your CPU does not support Parakeet models (requires AVX2). Switched to Whisper Small.",
+                                "message": format!("Your CPU does not support {} models. Switched to Whisper Small.", engine.name()),
                                 "original_model": model_info.id,
                                 "fallback_model": "small"
                             })
@@ -309,6 +324,7 @@ pub fn run() {
             commands::settings::change_autostart_setting,
             commands::settings::change_translate_to_english_setting,
             commands::settings::change_selected_language_setting,
+            commands::settings::change_allowed_languages_setting,
             commands::settings::change_overlay_position_setting,
             commands::settings::change_debug_mode_setting,
             commands::settings::change_word_correction_threshold_setting,
@@ -320,7 +336,25 @@ pub fn run() {
             commands::settings::get_openrouter_api_key_setting,
             commands::settings::change_openrouter_api_key_setting,
             commands::settings::change_openrouter_model_setting,
+            commands::settings::change_openrouter_fallback_models_setting,
             commands::settings::change_custom_instructions_setting,
+            commands::settings::change_user_name_setting,
+            commands::settings::change_ghostwriter_provider_setting,
+            commands::settings::change_ghostwriter_streaming_setting,
+            commands::settings::change_ghostwriter_max_retries_setting,
+            commands::settings::change_ghostwriter_guardrails_setting,
+            commands::settings::change_accurate_final_pass_setting,
+            commands::settings::change_long_form_dictation_setting,
+            commands::settings::change_low_confidence_threshold_setting,
+            commands::settings::change_ghostwriter_verify_hints_setting,
+            commands::settings::change_ghostwriter_monthly_budget_setting,
+            commands::settings::change_decoding_params_setting,
+            commands::settings::change_openai_compatible_base_url_setting,
+            commands::settings::change_openai_compatible_model_setting,
+            commands::settings::change_openai_compatible_fallback_models_setting,
+            commands::settings::get_openai_compatible_api_key_setting,
+            commands::settings::change_openai_compatible_api_key_setting,
+            commands::ghostwriter::list_ghostwriter_models,
             trigger_update_check,
             commands::cancel_operation,
             commands::get_app_dir_path,
@@ -331,6 +365,7 @@ pub fn run() {
             commands::models::delete_model,
             commands::models::cancel_download,
             commands::models::set_active_model,
+            commands::models::set_streaming_model,
             commands::models::get_current_model,
             commands::models::get_transcription_model_status,
             commands::models::is_model_loading,
@@ -350,9 +385,20 @@ pub fn run() {
             commands::transcription::set_model_unload_timeout,
             commands::transcription::get_model_load_status,
             commands::transcription::unload_model_manually,
+            commands::transcription::get_transcription_queue_metrics,
             commands::history::get_history_entries,
             commands::history::toggle_history_entry_saved,
             commands::history::get_audio_file_path,
+            commands::history::get_transcription_segments,
+            commands::history::export_subtitles,
+            commands::history::retranscribe_history_entry,
+            commands::history::get_transcription_versions,
+            commands::history::delete_transcription_version,
+            commands::import::import_audio_file,
+            commands::import::enqueue_transcription_jobs,
+            commands::import::get_transcription_jobs,
+            commands::import::cancel_transcription_job,
+            commands::import::clear_finished_transcription_jobs,
             commands::history::delete_history_entry,
             commands::history::update_history_limit,
             commands::history::search_transcriptions,
@@ -368,6 +414,10 @@ pub fn run() {
             commands::profile::delete_profile,
             commands::profile::get_profile_by_name,
             commands::profile::get_profile_stats,
+            commands::profile::get_profile_examples,
+            commands::profile::add_profile_example,
+            commands::profile::update_profile_example,
+            commands::profile::delete_profile_example,
             commands::tag::get_tags,
             commands::tag::get_tag,
             commands::tag::search_tags,
@@ -0,0 +1,215 @@
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::managers::transcription::{TimedTranscription, TranscriptionManager, MAX_CHUNK_SECONDS};
+use anyhow::Result;
+use log::{debug, error};
+use std::sync::{mpsc, Arc, Mutex};
+
+const SAMPLE_RATE: usize = WHISPER_SAMPLE_RATE as usize;
+
+/// Chunks are cut at the first pause after this much speech
+const MIN_CHUNK_SECONDS: usize = 10;
+
+/// Collects recorded speech and cuts it into chunks for transcription: at a pause once
+/// a chunk has `MIN_CHUNK_SECONDS`, or hard at `MAX_CHUNK_SECONDS` when there is none.
+/// Holds at most one chunk of audio.
+#[derive(Debug, Default)]
+pub struct ChunkBuffer {
+    pending: Vec<f32>,
+    /// Samples already cut into chunks, i.e. the offset of `pending` in the recording
+    consumed: usize,
+}
+
+impl ChunkBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds recorded speech; `pause` is true when it ended at a pause. Returns the
+    /// chunks that are ready, with their offset in samples.
+    pub fn push(&mut self, samples: &[f32], pause: bool) -> Vec<(usize, Vec<f32>)> {
+        let max_samples = MAX_CHUNK_SECONDS * SAMPLE_RATE;
+        let mut chunks = Vec::new();
+        self.pending.extend_from_slice(samples);
+
+        while self.pending.len() >= max_samples {
+            let rest = self.pending.split_off(max_samples);
+            chunks.push(self.cut(rest));
+        }
+        if pause && self.pending.len() >= MIN_CHUNK_SECONDS * SAMPLE_RATE {
+            chunks.push(self.cut(Vec::new()));
+        }
+        chunks
+    }
+
+    /// Samples pushed so far
+    pub fn received(&self) -> usize {
+        self.consumed + self.pending.len()
+    }
+
+    /// Whatever is left at the end of the recording, with its offset
+    pub fn take_rest(&mut self) -> Option<(usize, Vec<f32>)> {
+        (!self.pending.is_empty()).then(|| self.cut(Vec::new()))
+    }
+
+    /// Returns the pending audio as a chunk and keeps `rest` as the new pending audio
+    fn cut(&mut self, rest: Vec<f32>) -> (usize, Vec<f32>) {
+        let offset = self.consumed;
+        let chunk = std::mem::replace(&mut self.pending, rest);
+        self.consumed += chunk.len();
+        (offset, chunk)
+    }
+}
+
+/// Transcribes a long recording while it's being made, one chunk at a time, so only
+/// the current chunk and the text so far are kept in memory.
+#[derive(Default)]
+pub struct LongFormSession {
+    buffer: ChunkBuffer,
+    /// Text of the last finished chunk, used to prompt the next one
+    last_text: Arc<Mutex<Option<String>>>,
+    /// Offset in samples and result of each queued chunk, in recording order
+    chunks: Vec<(usize, mpsc::Receiver<Result<TimedTranscription>>)>,
+}
+
+impl LongFormSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds recorded speech and queues any chunk that is ready
+    pub fn push(&mut self, tm: &TranscriptionManager, samples: &[f32], pause: bool) {
+        for (offset, chunk) in self.buffer.push(samples, pause) {
+            self.submit(tm, offset, chunk);
+        }
+    }
+
+    /// Samples pushed so far; the rest of the recording goes to `finish`
+    pub fn received(&self) -> usize {
+        self.buffer.received()
+    }
+
+    /// Queues `tail`, the part of the recording that wasn't pushed (segments still in
+    /// flight when recording stopped), and the rest of the buffer, then waits for every
+    /// chunk
+    pub fn finish(mut self, tm: &TranscriptionManager, tail: &[f32]) -> TimedTranscription {
+        self.push(tm, tail, false);
+        if let Some((offset, mut chunk)) = self.buffer.take_rest() {
+            // Whisper needs at least a second of audio
+            if chunk.len() < SAMPLE_RATE {
+                chunk.resize(SAMPLE_RATE * 5 / 4, 0.0);
+            }
+            self.submit(tm, offset, chunk);
+        }
+        debug!("Waiting for {} long-form chunks", self.chunks.len());
+
+        let mut texts: Vec<String> = Vec::new();
+        let mut segments = Vec::new();
+        let mut language = None;
+        for (offset, rx) in self.chunks {
+            let transcription = match rx.recv() {
+                Ok(Ok(transcription)) => transcription,
+                Ok(Err(e)) => {
+                    error!("Long-form chunk at sample {} failed: {}", offset, e);
+                    continue;
+                }
+                Err(_) => {
+                    error!("Long-form chunk at sample {} was dropped", offset);
+                    continue;
+                }
+            };
+            let offset = offset as f32 / SAMPLE_RATE as f32;
+            if !transcription.text.is_empty() {
+                language = language.or(transcription.language);
+                texts.push(transcription.text);
+            }
+            segments.extend(
+                transcription
+                    .segments
+                    .into_iter()
+                    .map(|s| s.shifted(offset)),
+            );
+        }
+
+        TimedTranscription {
+            text: texts.join(" "),
+            segments,
+            language,
+        }
+    }
+
+    fn submit(&mut self, tm: &TranscriptionManager, offset: usize, chunk: Vec<f32>) {
+        debug!(
+            "Queueing long-form chunk at {:.1}s ({} samples)",
+            offset as f32 / SAMPLE_RATE as f32,
+            chunk.len()
+        );
+        let (tx, rx) = mpsc::channel();
+        let prompt_text = self.last_text.clone();
+        let result_text = self.last_text.clone();
+        // Chunks run in order, so the previous chunk has finished when this one starts
+        tm.transcribe_chunk(
+            chunk,
+            move || prompt_text.lock().unwrap().clone(),
+            move |result| {
+                if let Ok(transcription) = &result {
+                    if !transcription.text.is_empty() {
+                        *result_text.lock().unwrap() = Some(transcription.text.clone());
+                    }
+                }
+                let _ = tx.send(result);
+            },
+        );
+        self.chunks.push((offset, rx));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkBuffer, MAX_CHUNK_SECONDS, MIN_CHUNK_SECONDS, SAMPLE_RATE};
+
+    fn seconds(n: usize) -> Vec<f32> {
+        vec![0.1; n * SAMPLE_RATE]
+    }
+
+    #[test]
+    fn test_cuts_at_pause_after_min_length() {
+        let mut buffer = ChunkBuffer::new();
+        assert!(buffer.push(&seconds(4), true).is_empty());
+        assert!(buffer.push(&seconds(MIN_CHUNK_SECONDS), false).is_empty());
+
+        let chunks = buffer.push(&seconds(1), true);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].0, 0);
+        assert_eq!(chunks[0].1.len(), (MIN_CHUNK_SECONDS + 5) * SAMPLE_RATE);
+
+        // The next chunk starts where this one ended
+        buffer.push(&seconds(2), false);
+        assert_eq!(buffer.received(), (MIN_CHUNK_SECONDS + 7) * SAMPLE_RATE);
+        let (offset, rest) = buffer.take_rest().unwrap();
+        assert_eq!(offset, (MIN_CHUNK_SECONDS + 5) * SAMPLE_RATE);
+        assert_eq!(rest.len(), 2 * SAMPLE_RATE);
+        assert!(buffer.take_rest().is_none());
+    }
+
+    #[test]
+    fn test_cuts_hard_without_pause() {
+        let mut buffer = ChunkBuffer::new();
+        let chunks = buffer.push(&seconds(MAX_CHUNK_SECONDS * 2 + 3), false);
+        let max_samples = MAX_CHUNK_SECONDS * SAMPLE_RATE;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0, 0);
+        assert_eq!(chunks[1].0, max_samples);
+        assert!(chunks.iter().all(|(_, chunk)| chunk.len() == max_samples));
+
+        let (offset, rest) = buffer.take_rest().unwrap();
+        assert_eq!(offset, 2 * max_samples);
+        assert_eq!(rest.len(), 3 * SAMPLE_RATE);
+    }
+
+    #[test]
+    fn test_empty_buffer_has_no_rest() {
+        let mut buffer = ChunkBuffer::new();
+        assert!(buffer.push(&[], true).is_empty());
+        assert!(buffer.take_rest().is_none());
+    }
+}
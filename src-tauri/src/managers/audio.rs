@@ -4,8 +4,9 @@ use crate::settings::get_settings;
 use crate::utils;
 use log::{debug, error, info};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tauri::{Emitter, Manager};
 
 const WHISPER_SAMPLE_RATE: usize = 16000;
@@ -15,6 +16,74 @@ const WHISPER_SAMPLE_RATE: usize = 16000;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AudioSegmentEvent {
     pub samples: Vec<f32>,
+    /// True when the segment ended at a pause in speech (or the end of the recording)
+    /// rather than being cut for streaming
+    #[serde(default)]
+    pub pause: bool,
+}
+
+/// Audio from a finished recording
+#[derive(Debug)]
+pub enum RecordedAudio {
+    Samples(Vec<f32>),
+    /// Long-form recording written to a temporary 16 kHz WAV file while recording
+    Spilled {
+        path: PathBuf,
+        sample_count: usize,
+    },
+}
+
+impl RecordedAudio {
+    pub fn sample_count(&self) -> usize {
+        match self {
+            RecordedAudio::Samples(samples) => samples.len(),
+            RecordedAudio::Spilled { sample_count, .. } => *sample_count,
+        }
+    }
+
+    /// Samples from `start` to the end of the recording
+    pub fn read_from(&self, start: usize) -> Result<Vec<f32>, anyhow::Error> {
+        match self {
+            RecordedAudio::Samples(samples) => {
+                Ok(samples.get(start..).unwrap_or_default().to_vec())
+            }
+            RecordedAudio::Spilled { path, sample_count } => {
+                if start >= *sample_count {
+                    return Ok(Vec::new());
+                }
+                let mut reader = hound::WavReader::open(path)?;
+                reader.seek(start as u32)?;
+                reader
+                    .samples::<i16>()
+                    .map(|s| Ok(s? as f32 / i16::MAX as f32))
+                    .collect()
+            }
+        }
+    }
+
+    /// Deletes the temporary file of a spilled recording that won't be saved
+    pub fn discard(self) {
+        if let RecordedAudio::Spilled { path, .. } = self {
+            remove_spill_file(&path);
+        }
+    }
+}
+
+fn remove_spill_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            error!("Failed to remove spilled recording {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Temporary file for a long-form recording
+fn new_spill_path() -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("leadrscribe-recording-{millis}.wav"))
 }
 
 #[derive(Clone, Debug)]
@@ -38,6 +107,8 @@ struct RecordingInner {
     is_open: bool,
     is_recording: bool,
     initial_volume: Option<u8>,
+    /// File the current recording is written to, for long-form dictation
+    spill_path: Option<PathBuf>,
 }
 
 /* ──────────────────────────────────────────────────────────────── */
@@ -62,13 +133,15 @@ fn create_audio_recorder(
         })
         .with_segment_callback({
             let app_handle = app_handle.clone();
-            move |samples| {
-                debug!("Audio segment detected: samples={}", samples.len());
+            move |samples, pause| {
+                debug!(
+                    "Audio segment detected: samples={}, pause={}",
+                    samples.len(),
+                    pause
+                );
 
                 // Emit segment event for processing
-                let _ = app_handle.emit("audio-segment", AudioSegmentEvent {
-                    samples,
-                });
+                let _ = app_handle.emit("audio-segment", AudioSegmentEvent { samples, pause });
             }
         });
 
@@ -102,6 +175,7 @@ impl AudioRecordingManager {
                 is_open: false,
                 is_recording: false,
                 initial_volume: None,
+                spill_path: None,
             })),
             app_handle: app.clone(),
         };
@@ -209,6 +283,9 @@ impl AudioRecordingManager {
                 let _ = rec.stop();
             }
             inner.is_recording = false;
+            if let Some(path) = inner.spill_path.take() {
+                remove_spill_file(&path);
+            }
         }
 
         if let Some(rec) = inner.recorder.as_mut() {
@@ -263,8 +340,17 @@ impl AudioRecordingManager {
             inner = self.inner.lock().unwrap();
         }
 
+        let spill_path = get_settings(&self.app_handle)
+            .long_form_dictation
+            .then(new_spill_path);
+
         if let Some(rec) = inner.recorder.as_ref() {
-            if rec.start().is_ok() {
+            let started = match &spill_path {
+                Some(path) => rec.start_spilling(path.clone()),
+                None => rec.start(),
+            };
+            if started.is_ok() {
+                inner.spill_path = spill_path;
                 inner.is_recording = true;
                 inner.state = RecordingState::Recording {
                     binding_id: binding_id.to_string(),
@@ -286,7 +372,7 @@ impl AudioRecordingManager {
         Ok(())
     }
 
-    pub fn stop_recording(&self, binding_id: &str) -> Option<Vec<f32>> {
+    pub fn stop_recording(&self, binding_id: &str) -> Option<RecordedAudio> {
         let mut inner = self.inner.lock().unwrap();
 
         match &inner.state {
@@ -307,6 +393,7 @@ impl AudioRecordingManager {
                 };
 
                 inner.is_recording = false;
+                let spill_path = inner.spill_path.take();
 
                 // In on-demand mode turn the mic off again
                 let should_close = matches!(inner.mode, MicrophoneMode::OnDemand);
@@ -316,14 +403,29 @@ impl AudioRecordingManager {
                     self.stop_microphone_stream();
                 }
 
+                // Samples come back in memory if the spill file couldn't be created
+                if let Some(path) = spill_path.filter(|_| samples.is_empty()) {
+                    match hound::WavReader::open(&path) {
+                        Ok(reader) => {
+                            let sample_count = reader.duration() as usize;
+                            return Some(RecordedAudio::Spilled { path, sample_count });
+                        }
+                        Err(e) => {
+                            error!("Failed to open spilled recording {:?}: {}", path, e);
+                            remove_spill_file(&path);
+                            return Some(RecordedAudio::Samples(Vec::new()));
+                        }
+                    }
+                }
+
                 // Pad if very short
                 let s_len = samples.len();
                 if s_len < WHISPER_SAMPLE_RATE && s_len > 0 {
                     let mut padded = samples;
                     padded.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
-                    Some(padded)
+                    Some(RecordedAudio::Samples(padded))
                 } else {
-                    Some(samples)
+                    Some(RecordedAudio::Samples(samples))
                 }
             }
             _ => None,
@@ -342,6 +444,9 @@ impl AudioRecordingManager {
             }
 
             inner.is_recording = false;
+            if let Some(path) = inner.spill_path.take() {
+                remove_spill_file(&path);
+            }
 
             // In on-demand mode turn the mic off again
             let should_close = matches!(inner.mode, MicrophoneMode::OnDemand);
@@ -1,14 +1,19 @@
 use anyhow::Result;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Datelike, Local, Utc};
 use log::{debug, error};
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_sql::{Migration, MigrationKind};
 
 use crate::audio_toolkit::save_wav_file;
+use crate::ghostwriter::{Rewrite, Usage};
+use crate::language::DetectedLanguage;
+use crate::managers::audio::RecordedAudio;
+use crate::managers::transcription::{TranscriptSegment, WordTiming};
+use crate::settings::DecodingParams;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HistoryEntry {
@@ -23,10 +28,25 @@ pub struct HistoryEntry {
     pub notes: Option<String>,
     pub duration_seconds: Option<f64>,
     pub word_count: Option<i32>,
+    /// Model that produced `ghostwritten_text` (may be a fallback model)
+    pub ghostwriter_model: Option<String>,
+    pub prompt_tokens: Option<i64>,
+    pub completion_tokens: Option<i64>,
+    /// Ghostwriting cost in USD, when the provider reports it
+    pub ghostwriter_cost: Option<f64>,
+    /// Whisper language code of the dictation
+    pub language: Option<String>,
+    /// Detection confidence; None when the language was set explicitly
+    pub language_probability: Option<f64>,
 }
 
+/// Columns selected by every HistoryEntry query, in `row_to_history_entry` order.
+const HISTORY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, ghostwritten_text,
+     profile_id, notes, duration_seconds, word_count, ghostwriter_model, prompt_tokens, completion_tokens,
+     ghostwriter_cost, language, language_probability";
+
 /// Shared mapping function to convert a database row to a HistoryEntry.
-/// Used by all query methods to avoid duplicating the 11-field mapping.
+/// Used by all query methods to avoid duplicating the field mapping.
 fn row_to_history_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
     Ok(HistoryEntry {
         id: row.get("id")?,
@@ -40,9 +60,43 @@ fn row_to_history_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
         notes: row.get("notes")?,
         duration_seconds: row.get("duration_seconds")?,
         word_count: row.get("word_count")?,
+        ghostwriter_model: row.get("ghostwriter_model")?,
+        prompt_tokens: row.get("prompt_tokens")?,
+        completion_tokens: row.get("completion_tokens")?,
+        ghostwriter_cost: row.get("ghostwriter_cost")?,
+        language: row.get("language")?,
+        language_probability: row.get("language_probability")?,
     })
 }
 
+/// FTS5 search over transcriptions, ghostwritten text and notes, best matches first
+fn search_history(conn: &Connection, query: &str, limit: usize) -> Result<Vec<HistoryEntry>> {
+    // Qualified because the FTS table has columns of the same names
+    let columns = HISTORY_COLUMNS
+        .split(',')
+        .map(|column| format!("h.{}", column.trim()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
+         FROM transcription_history h
+         INNER JOIN transcription_fts fts ON h.id = fts.rowid
+         WHERE transcription_fts MATCH ?1
+         ORDER BY rank
+         LIMIT ?2",
+        columns
+    ))?;
+
+    let entries = stmt.query_map(params![query, limit as i64], row_to_history_entry)?;
+
+    let mut result = Vec::new();
+    for entry in entries {
+        result.push(entry?);
+    }
+
+    Ok(result)
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Profile {
     pub id: i64,
@@ -51,10 +105,35 @@ pub struct Profile {
     pub color: String,
     pub icon: String,
     pub custom_instructions: Option<String>,
+    /// Ghostwriter provider override ("openrouter" / "openai_compatible"), None = use global setting
+    pub ghostwriter_provider: Option<String>,
+    /// Model override for this profile, None = the provider's configured model
+    pub ghostwriter_model: Option<String>,
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    /// Completion token limit, None = scaled to the length of the dictation
+    pub max_tokens: Option<i64>,
+    /// Whisper decoding overrides, unset fields use the global decoding settings
+    pub decoding_params: DecodingParams,
+    /// Names and terms Whisper is prompted with while this profile is active
+    pub vocabulary: Vec<String>,
     pub created_at: i64,
     pub updated_at: i64,
 }
 
+/// An alternative transcript of a history entry's recording, made with another model
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TranscriptionVersion {
+    pub id: i64,
+    pub history_id: i64,
+    pub model_id: String,
+    /// Language the model was run with, None = the language setting at the time
+    pub language: Option<String>,
+    pub translate: bool,
+    pub transcription_text: String,
+    pub created_at: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Tag {
     pub id: i64,
@@ -186,6 +265,124 @@ impl HistoryManager {
                 END;",
                 kind: MigrationKind::Up,
             },
+            Migration {
+                version: 7,
+                description: "add_profile_ghostwriter_provider",
+                sql: "ALTER TABLE profiles ADD COLUMN ghostwriter_provider TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 8,
+                description: "add_ghostwriter_model",
+                sql: "ALTER TABLE transcription_history ADD COLUMN ghostwriter_model TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 9,
+                description: "add_ghostwriter_usage",
+                // The usage table is a ledger of every ghostwriter request; unlike history
+                // entries it is never pruned, so monthly spend stays accurate.
+                sql: "ALTER TABLE transcription_history ADD COLUMN prompt_tokens INTEGER;
+                ALTER TABLE transcription_history ADD COLUMN completion_tokens INTEGER;
+                ALTER TABLE transcription_history ADD COLUMN ghostwriter_cost REAL;
+                CREATE TABLE IF NOT EXISTS ghostwriter_usage (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    timestamp INTEGER NOT NULL,
+                    model TEXT NOT NULL,
+                    prompt_tokens INTEGER NOT NULL,
+                    completion_tokens INTEGER NOT NULL,
+                    cost REAL
+                );
+                CREATE INDEX IF NOT EXISTS idx_ghostwriter_usage_timestamp ON ghostwriter_usage(timestamp);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 10,
+                description: "add_profile_generation_params",
+                sql: "ALTER TABLE profiles ADD COLUMN ghostwriter_model TEXT;
+                ALTER TABLE profiles ADD COLUMN temperature REAL;
+                ALTER TABLE profiles ADD COLUMN top_p REAL;
+                ALTER TABLE profiles ADD COLUMN max_tokens INTEGER;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 11,
+                description: "create_profile_examples",
+                sql: "CREATE TABLE IF NOT EXISTS profile_examples (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    profile_id INTEGER NOT NULL REFERENCES profiles(id) ON DELETE CASCADE,
+                    input TEXT NOT NULL,
+                    output TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_profile_examples_profile ON profile_examples(profile_id);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 12,
+                description: "create_transcription_segments",
+                sql: "CREATE TABLE IF NOT EXISTS transcription_segments (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    history_id INTEGER NOT NULL REFERENCES transcription_history(id) ON DELETE CASCADE,
+                    segment_index INTEGER NOT NULL,
+                    start_time REAL NOT NULL,
+                    end_time REAL NOT NULL,
+                    text TEXT NOT NULL,
+                    words TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_transcription_segments_history ON transcription_segments(history_id, segment_index);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 13,
+                description: "create_transcription_jobs",
+                sql: "CREATE TABLE IF NOT EXISTS transcription_jobs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    file_path TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'queued',
+                    progress REAL NOT NULL DEFAULT 0,
+                    history_id INTEGER,
+                    error TEXT,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_transcription_jobs_status ON transcription_jobs(status, id);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 14,
+                description: "create_transcription_versions",
+                sql: "CREATE TABLE IF NOT EXISTS transcription_versions (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    history_id INTEGER NOT NULL REFERENCES transcription_history(id) ON DELETE CASCADE,
+                    model_id TEXT NOT NULL,
+                    language TEXT,
+                    translate INTEGER NOT NULL DEFAULT 0,
+                    transcription_text TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_transcription_versions_history ON transcription_versions(history_id);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 15,
+                description: "add_profile_decoding_params",
+                sql: "ALTER TABLE profiles ADD COLUMN decoding_params TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 16,
+                description: "add_history_language",
+                sql: "ALTER TABLE transcription_history ADD COLUMN language TEXT;
+                ALTER TABLE transcription_history ADD COLUMN language_probability REAL;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 17,
+                description: "add_profile_vocabulary",
+                sql: "ALTER TABLE profiles ADD COLUMN vocabulary TEXT;",
+                kind: MigrationKind::Up,
+            },
         ]
     }
 
@@ -199,7 +396,10 @@ impl HistoryManager {
         // Initialize default profiles if none exist
         self.init_default_profiles(&conn)?;
 
-        debug!("Database post-migration init complete at: {:?}", self.db_path);
+        debug!(
+            "Database post-migration init complete at: {:?}",
+            self.db_path
+        );
         Ok(())
     }
 
@@ -239,18 +439,20 @@ impl HistoryManager {
         Ok(Connection::open(&self.db_path)?)
     }
 
-    /// Save a transcription to history (both database and WAV file)
+    /// Save a transcription to history (both database and WAV file). A spilled
+    /// recording's file is moved into the recordings dir.
+    /// Returns the new entry's id, or None when history is disabled.
     pub async fn save_transcription(
         &self,
-        audio_samples: Vec<f32>,
+        audio: &RecordedAudio,
         transcription_text: String,
-        ghostwritten_text: Option<String>,
+        ghostwritten: Option<Rewrite>,
         profile_id: Option<i64>,
         duration_seconds: Option<f64>,
-    ) -> Result<()> {
+    ) -> Result<Option<i64>> {
         // If history limit is 0, do not save at all.
         if crate::settings::get_history_limit(&self.app_handle) == 0 {
-            return Ok(());
+            return Ok(None);
         }
 
         let timestamp = Utc::now().timestamp();
@@ -262,15 +464,25 @@ impl HistoryManager {
 
         // Save WAV file
         let file_path = self.recordings_dir.join(&file_name);
-        save_wav_file(file_path, &audio_samples).await?;
+        match audio {
+            RecordedAudio::Samples(samples) => save_wav_file(file_path, samples).await?,
+            RecordedAudio::Spilled { path, .. } => {
+                // The temp dir may be on another file system, where rename fails
+                if fs::rename(path, &file_path).is_err() {
+                    fs::copy(path, &file_path)?;
+                    fs::remove_file(path)?;
+                }
+                debug!("Moved spilled recording to {:?}", file_path);
+            }
+        }
 
         // Save to database with new fields
-        self.save_to_database(
+        let id = self.save_to_database(
             file_name,
             timestamp,
             title,
             transcription_text,
-            ghostwritten_text,
+            ghostwritten,
             profile_id,
             duration_seconds,
             word_count,
@@ -284,6 +496,194 @@ impl HistoryManager {
             error!("Failed to emit history-updated event: {}", e);
         }
 
+        Ok(Some(id))
+    }
+
+    /// Save the transcription of an existing audio file, copying the file into the
+    /// recordings dir. Returns the new entry's id.
+    pub async fn save_imported_transcription(
+        &self,
+        source: &Path,
+        transcription_text: String,
+        duration_seconds: f64,
+    ) -> Result<i64> {
+        if crate::settings::get_history_limit(&self.app_handle) == 0 {
+            anyhow::bail!("History is disabled. Set a history limit above 0 to import recordings.");
+        }
+
+        let timestamp = Utc::now().timestamp();
+        let extension = source
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("wav")
+            .to_lowercase();
+        // Milliseconds keep file names unique when several files are imported at once
+        let file_name = format!(
+            "leadrscribe-import-{}.{}",
+            Utc::now().timestamp_millis(),
+            extension
+        );
+        fs::copy(source, self.recordings_dir.join(&file_name))?;
+
+        let title = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.format_timestamp_title(timestamp));
+        let word_count = transcription_text.split_whitespace().count() as i32;
+
+        let id = self.save_to_database(
+            file_name,
+            timestamp,
+            title,
+            transcription_text,
+            None,
+            None,
+            Some(duration_seconds),
+            word_count,
+        )?;
+
+        self.cleanup_old_entries()?;
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(id)
+    }
+
+    /// Stores segment and word timings for a history entry, replacing any existing ones
+    pub fn save_transcription_segments(
+        &self,
+        history_id: i64,
+        segments: &[TranscriptSegment],
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM transcription_segments WHERE history_id = ?1",
+            params![history_id],
+        )?;
+        for (index, segment) in segments.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO transcription_segments (history_id, segment_index, start_time, end_time, text, words)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    history_id,
+                    index as i64,
+                    segment.start,
+                    segment.end,
+                    segment.text,
+                    serde_json::to_string(&segment.words)?
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        debug!(
+            "Saved {} segments for history entry {}",
+            segments.len(),
+            history_id
+        );
+        Ok(())
+    }
+
+    pub async fn get_transcription_segments(
+        &self,
+        history_id: i64,
+    ) -> Result<Vec<TranscriptSegment>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT start_time, end_time, text, words FROM transcription_segments
+             WHERE history_id = ?1 ORDER BY segment_index",
+        )?;
+
+        let rows = stmt.query_map(params![history_id], |row| {
+            Ok((
+                row.get::<_, f32>("start_time")?,
+                row.get::<_, f32>("end_time")?,
+                row.get::<_, String>("text")?,
+                row.get::<_, String>("words")?,
+            ))
+        })?;
+
+        let mut segments = Vec::new();
+        for row in rows {
+            let (start, end, text, words) = row?;
+            let words: Vec<WordTiming> = serde_json::from_str(&words)?;
+            segments.push(TranscriptSegment {
+                text,
+                start,
+                end,
+                words,
+            });
+        }
+
+        Ok(segments)
+    }
+
+    pub fn add_transcription_version(
+        &self,
+        history_id: i64,
+        model_id: &str,
+        language: Option<&str>,
+        translate: bool,
+        transcription_text: &str,
+    ) -> Result<TranscriptionVersion> {
+        let conn = self.get_connection()?;
+        let now = Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO transcription_versions (history_id, model_id, language, translate, transcription_text, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![history_id, model_id, language, translate, transcription_text, now],
+        )?;
+
+        Ok(TranscriptionVersion {
+            id: conn.last_insert_rowid(),
+            history_id,
+            model_id: model_id.to_string(),
+            language: language.map(str::to_string),
+            translate,
+            transcription_text: transcription_text.to_string(),
+            created_at: now,
+        })
+    }
+
+    pub async fn get_transcription_versions(
+        &self,
+        history_id: i64,
+    ) -> Result<Vec<TranscriptionVersion>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, history_id, model_id, language, translate, transcription_text, created_at
+             FROM transcription_versions WHERE history_id = ?1 ORDER BY created_at DESC, id DESC",
+        )?;
+
+        let rows = stmt.query_map(params![history_id], |row| {
+            Ok(TranscriptionVersion {
+                id: row.get("id")?,
+                history_id: row.get("history_id")?,
+                model_id: row.get("model_id")?,
+                language: row.get("language")?,
+                translate: row.get("translate")?,
+                transcription_text: row.get("transcription_text")?,
+                created_at: row.get("created_at")?,
+            })
+        })?;
+
+        let mut versions = Vec::new();
+        for row in rows {
+            versions.push(row?);
+        }
+        Ok(versions)
+    }
+
+    pub async fn delete_transcription_version(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "DELETE FROM transcription_versions WHERE id = ?1",
+            params![id],
+        )?;
+        debug!("Deleted transcription version {}", id);
         Ok(())
     }
 
@@ -293,21 +693,37 @@ impl HistoryManager {
         timestamp: i64,
         title: String,
         transcription_text: String,
-        ghostwritten_text: Option<String>,
+        ghostwritten: Option<Rewrite>,
         profile_id: Option<i64>,
         duration_seconds: Option<f64>,
         word_count: i32,
-    ) -> Result<()> {
+    ) -> Result<i64> {
+        let usage = ghostwritten
+            .as_ref()
+            .and_then(|rewrite| rewrite.usage.clone());
+        let (ghostwritten_text, ghostwriter_model) = match ghostwritten {
+            Some(rewrite) => (Some(rewrite.text), Some(rewrite.model)),
+            None => (None, None),
+        };
+        let prompt_tokens = usage.as_ref().map(|u| u.prompt_tokens);
+        let completion_tokens = usage.as_ref().map(|u| u.completion_tokens);
+        let ghostwriter_cost = usage.and_then(|u| u.cost);
+
         let conn = self.get_connection()?;
         conn.execute(
             "INSERT INTO transcription_history
-             (file_name, timestamp, saved, title, transcription_text, ghostwritten_text, profile_id, duration_seconds, word_count)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            params![file_name, timestamp, false, title, transcription_text, ghostwritten_text, profile_id, duration_seconds, word_count],
+             (file_name, timestamp, saved, title, transcription_text, ghostwritten_text, profile_id, duration_seconds, word_count, ghostwriter_model,
+              prompt_tokens, completion_tokens, ghostwriter_cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![file_name, timestamp, false, title, transcription_text, ghostwritten_text, profile_id, duration_seconds, word_count, ghostwriter_model,
+                    prompt_tokens, completion_tokens, ghostwriter_cost],
         )?;
 
-        debug!("Saved transcription to database with word_count: {}", word_count);
-        Ok(())
+        debug!(
+            "Saved transcription to database with word_count: {}",
+            word_count
+        );
+        Ok(conn.last_insert_rowid())
     }
 
     fn cleanup_old_entries(&self) -> Result<()> {
@@ -333,6 +749,14 @@ impl HistoryManager {
 
             for (id, file_name) in entries_to_delete {
                 // Delete database entry
+                conn.execute(
+                    "DELETE FROM transcription_segments WHERE history_id = ?1",
+                    params![id],
+                )?;
+                conn.execute(
+                    "DELETE FROM transcription_versions WHERE history_id = ?1",
+                    params![id],
+                )?;
                 conn.execute(
                     "DELETE FROM transcription_history WHERE id = ?1",
                     params![id],
@@ -357,11 +781,10 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, ghostwritten_text,
-             profile_id, notes, duration_seconds, word_count
-             FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            HISTORY_COLUMNS
+        ))?;
 
         let rows = stmt.query_map([], row_to_history_entry)?;
 
@@ -406,15 +829,12 @@ impl HistoryManager {
 
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, ghostwritten_text,
-             profile_id, notes, duration_seconds, word_count
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            HISTORY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], row_to_history_entry)
-            .optional()?;
+        let entry = stmt.query_row([id], row_to_history_entry).optional()?;
 
         Ok(entry)
     }
@@ -435,6 +855,14 @@ impl HistoryManager {
         }
 
         // Delete from database
+        conn.execute(
+            "DELETE FROM transcription_segments WHERE history_id = ?1",
+            params![id],
+        )?;
+        conn.execute(
+            "DELETE FROM transcription_versions WHERE history_id = ?1",
+            params![id],
+        )?;
         conn.execute(
             "DELETE FROM transcription_history WHERE id = ?1",
             params![id],
@@ -473,42 +901,27 @@ impl HistoryManager {
     ///
     /// # Returns
     /// Vector of HistoryEntry items matching the search query, ordered by relevance
-    pub async fn search_transcriptions(&self, query: &str, limit: usize) -> Result<Vec<HistoryEntry>> {
+    pub async fn search_transcriptions(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-
-        let mut stmt = conn.prepare(
-            "SELECT h.id, h.file_name, h.timestamp, h.saved, h.title,
-                    h.transcription_text, h.ghostwritten_text, h.profile_id,
-                    h.notes, h.duration_seconds, h.word_count
-             FROM transcription_history h
-             INNER JOIN transcription_fts fts ON h.id = fts.rowid
-             WHERE transcription_fts MATCH ?1
-             ORDER BY rank
-             LIMIT ?2"
-        )?;
-
-        let entries = stmt.query_map(params![query, limit as i64], row_to_history_entry)?;
-
-        let mut result = Vec::new();
-        for entry in entries {
-            result.push(entry?);
-        }
-
-        Ok(result)
+        search_history(&conn, query, limit)
     }
 
     /// Get transcriptions filtered by profile
     pub async fn get_by_profile(&self, profile_id: i64, limit: usize) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text,
-                    ghostwritten_text, profile_id, notes, duration_seconds, word_count
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM transcription_history
              WHERE profile_id = ?1
              ORDER BY timestamp DESC
-             LIMIT ?2"
-        )?;
+             LIMIT ?2",
+            HISTORY_COLUMNS
+        ))?;
 
         let entries = stmt.query_map(params![profile_id, limit as i64], row_to_history_entry)?;
 
@@ -529,16 +942,19 @@ impl HistoryManager {
     ) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text,
-                    ghostwritten_text, profile_id, notes, duration_seconds, word_count
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM transcription_history
              WHERE timestamp >= ?1 AND timestamp <= ?2
              ORDER BY timestamp DESC
-             LIMIT ?3"
-        )?;
+             LIMIT ?3",
+            HISTORY_COLUMNS
+        ))?;
 
-        let entries = stmt.query_map(params![start_timestamp, end_timestamp, limit as i64], row_to_history_entry)?;
+        let entries = stmt.query_map(
+            params![start_timestamp, end_timestamp, limit as i64],
+            row_to_history_entry,
+        )?;
 
         let mut result = Vec::new();
         for entry in entries {
@@ -552,14 +968,14 @@ impl HistoryManager {
     pub async fn get_saved_only(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text,
-                    ghostwritten_text, profile_id, notes, duration_seconds, word_count
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM transcription_history
              WHERE saved = 1
              ORDER BY timestamp DESC
-             LIMIT ?1"
-        )?;
+             LIMIT ?1",
+            HISTORY_COLUMNS
+        ))?;
 
         let entries = stmt.query_map(params![limit as i64], row_to_history_entry)?;
 
@@ -571,6 +987,23 @@ impl HistoryManager {
         Ok(result)
     }
 
+    /// Records the language a transcription was spoken in
+    pub fn save_language(&self, id: i64, language: &DetectedLanguage) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE transcription_history SET language = ?1, language_probability = ?2 WHERE id = ?3",
+            params![language.code, language.probability, id],
+        )?;
+
+        debug!("Saved language '{}' for entry {}", language.code, id);
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
     /// Update notes for a transcription
     pub async fn update_notes(&self, id: i64, notes: Option<String>) -> Result<()> {
         let conn = self.get_connection()?;
@@ -590,15 +1023,37 @@ impl HistoryManager {
         Ok(())
     }
 
-    /// Get statistics for the history
-    pub async fn get_stats(&self) -> Result<HistoryStats> {
+    /// Record the usage of one ghostwriter request in the usage ledger.
+    /// Recorded even when history saving is disabled, so budgets keep working.
+    pub fn record_ghostwriter_usage(&self, model: &str, usage: &Usage) -> Result<()> {
         let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO ghostwriter_usage (timestamp, model, prompt_tokens, completion_tokens, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![Utc::now().timestamp(), model, usage.prompt_tokens, usage.completion_tokens, usage.cost],
+        )?;
+        Ok(())
+    }
 
-        let total_count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM transcription_history",
-            [],
+    /// Ghostwriter spend (USD) since the start of the current local calendar month
+    pub fn get_month_ghostwriter_cost(&self) -> Result<f64> {
+        let conn = self.get_connection()?;
+        let cost: f64 = conn.query_row(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM ghostwriter_usage WHERE timestamp >= ?1",
+            params![start_of_month(Local::now())],
             |row| row.get(0),
         )?;
+        Ok(cost)
+    }
+
+    /// Get statistics for the history
+    pub async fn get_stats(&self) -> Result<HistoryStats> {
+        let conn = self.get_connection()?;
+
+        let total_count: i64 =
+            conn.query_row("SELECT COUNT(*) FROM transcription_history", [], |row| {
+                row.get(0)
+            })?;
 
         let total_duration: f64 = conn.query_row(
             "SELECT COALESCE(SUM(duration_seconds), 0.0) FROM transcription_history",
@@ -618,19 +1073,84 @@ impl HistoryManager {
             |row| row.get(0),
         )?;
 
+        let (total_prompt_tokens, total_completion_tokens, total_ghostwriter_cost): (i64, i64, f64) =
+            conn.query_row(
+                "SELECT COALESCE(SUM(prompt_tokens), 0), COALESCE(SUM(completion_tokens), 0), COALESCE(SUM(cost), 0.0)
+                 FROM ghostwriter_usage",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+
+        let month_ghostwriter_cost = self.get_month_ghostwriter_cost()?;
+
         Ok(HistoryStats {
             total_count,
             total_duration_seconds: total_duration,
             total_words,
             saved_count,
+            total_prompt_tokens,
+            total_completion_tokens,
+            total_ghostwriter_cost,
+            month_ghostwriter_cost,
         })
     }
 }
 
+/// Unix timestamp of midnight on the first day of `now`'s month (local time)
+fn start_of_month(now: DateTime<Local>) -> i64 {
+    now.date_naive()
+        .with_day(1)
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map(|start| start.timestamp())
+        .unwrap_or(0)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HistoryStats {
     pub total_count: i64,
     pub total_duration_seconds: f64,
     pub total_words: i64,
     pub saved_count: i64,
+    /// Ghostwriter usage totals, from the usage ledger (not affected by history pruning)
+    pub total_prompt_tokens: i64,
+    pub total_completion_tokens: i64,
+    pub total_ghostwriter_cost: f64,
+    pub month_ghostwriter_cost: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrated_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        for migration in HistoryManager::get_migrations() {
+            conn.execute_batch(migration.sql).unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn test_search_returns_full_entries() {
+        let conn = migrated_db();
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, language, language_probability)
+             VALUES ('a.wav', 1, 0, 'First', 'Bore da, sut mae', 'cy', 0.9),
+                    ('b.wav', 2, 0, 'Second', 'Meeting notes for Tuesday', 'en', NULL)",
+            [],
+        )
+        .unwrap();
+
+        let results = search_history(&conn, "meeting", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Second");
+        assert_eq!(results[0].language.as_deref(), Some("en"));
+        assert_eq!(results[0].language_probability, None);
+
+        let results = search_history(&conn, "bore", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].language.as_deref(), Some("cy"));
+        assert_eq!(results[0].language_probability, Some(0.9));
+    }
 }
@@ -0,0 +1,549 @@
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::{
+    decode_audio_file, split_on_silence, SileroVad, VoiceActivityDetector,
+    SUPPORTED_AUDIO_EXTENSIONS,
+};
+use crate::cpu_features;
+use crate::managers::history::HistoryManager;
+use crate::managers::inference_queue::JobPriority;
+use crate::managers::transcription::{TimedTranscription, TranscriptionManager, MAX_CHUNK_SECONDS};
+
+/// Share of a job's progress spent decoding, before any chunk is transcribed
+const DECODE_PROGRESS: f32 = 0.05;
+
+const JOB_COLUMNS: &str =
+    "id, file_path, status, progress, history_id, error, created_at, updated_at";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "queued" => JobStatus::Queued,
+            "running" => JobStatus::Running,
+            "completed" => JobStatus::Completed,
+            "cancelled" => JobStatus::Cancelled,
+            _ => JobStatus::Failed,
+        }
+    }
+}
+
+/// A file waiting for, or done with, background transcription into history
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TranscriptionJob {
+    pub id: i64,
+    pub file_path: String,
+    pub status: JobStatus,
+    /// 0.0 to 1.0
+    pub progress: f64,
+    /// History entry created by a completed job
+    pub history_id: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+fn row_to_job(row: &rusqlite::Row) -> rusqlite::Result<TranscriptionJob> {
+    Ok(TranscriptionJob {
+        id: row.get("id")?,
+        file_path: row.get("file_path")?,
+        status: JobStatus::parse(&row.get::<_, String>("status")?),
+        progress: row.get("progress")?,
+        history_id: row.get("history_id")?,
+        error: row.get("error")?,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Returned through anyhow when a job is cancelled between chunks
+#[derive(Debug)]
+struct JobCancelled;
+
+impl fmt::Display for JobCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cancelled")
+    }
+}
+
+impl std::error::Error for JobCancelled {}
+
+/// Transcribes existing audio files into history, either directly or through a
+/// persistent job queue worked off by a background thread
+#[derive(Clone)]
+pub struct ImportManager {
+    app_handle: AppHandle,
+    db_path: PathBuf,
+    transcription_manager: Arc<TranscriptionManager>,
+    history_manager: Arc<HistoryManager>,
+    /// Set when new jobs are queued, wakes the worker
+    work_pending: Arc<(Mutex<bool>, Condvar)>,
+    /// Job the worker is currently transcribing
+    running_job: Arc<Mutex<Option<i64>>>,
+    cancel_running: Arc<AtomicBool>,
+}
+
+impl ImportManager {
+    pub fn new(
+        app_handle: &AppHandle,
+        transcription_manager: Arc<TranscriptionManager>,
+        history_manager: Arc<HistoryManager>,
+    ) -> Result<Self> {
+        let app_data_dir = app_handle.path().app_data_dir()?;
+
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            db_path: app_data_dir.join("history.db"),
+            transcription_manager,
+            history_manager,
+            work_pending: Arc::new((Mutex::new(true), Condvar::new())),
+            running_job: Arc::new(Mutex::new(None)),
+            cancel_running: Arc::new(AtomicBool::new(false)),
+        };
+
+        let worker = manager.clone();
+        thread::spawn(move || worker.run_worker());
+
+        Ok(manager)
+    }
+
+    fn get_connection(&self) -> Result<Connection> {
+        Ok(Connection::open(&self.db_path)?)
+    }
+
+    /// Decodes, splits and transcribes `path`, then saves it as a history entry with
+    /// a copy of the file. Returns the new entry's id.
+    pub async fn import_file(&self, path: PathBuf) -> Result<i64> {
+        self.import_file_with(path, |_| {}, || false).await
+    }
+
+    async fn import_file_with(
+        &self,
+        path: PathBuf,
+        on_progress: impl Fn(f32) + Send + 'static,
+        is_cancelled: impl Fn() -> bool + Send + 'static,
+    ) -> Result<i64> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        if !SUPPORTED_AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+            anyhow::bail!(
+                "Unsupported file type. Supported formats: {}",
+                SUPPORTED_AUDIO_EXTENSIONS.join(", ")
+            );
+        }
+        if !path.is_file() {
+            anyhow::bail!("File not found: {}", path.display());
+        }
+
+        // Silero runs on ONNX Runtime, which needs AVX2
+        if !cpu_features::check_cpu_capabilities().supports_parakeet {
+            anyhow::bail!(
+                "Your CPU does not support the AVX2 instructions required to split recordings into speech chunks."
+            );
+        }
+
+        let vad_path = self
+            .app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))?;
+
+        let tm = Arc::clone(&self.transcription_manager);
+        let source = path.clone();
+        let (sample_count, transcription) = tauri::async_runtime::spawn_blocking(move || {
+            transcribe_file(&tm, &source, &vad_path, on_progress, is_cancelled)
+        })
+        .await??;
+
+        let duration_seconds = sample_count as f64 / WHISPER_SAMPLE_RATE as f64;
+        let id = self
+            .history_manager
+            .save_imported_transcription(&path, transcription.text, duration_seconds)
+            .await?;
+        self.history_manager
+            .save_transcription_segments(id, &transcription.segments)?;
+        if let Some(language) = &transcription.language {
+            self.history_manager.save_language(id, language)?;
+        }
+
+        info!("Imported {} as history entry {}", path.display(), id);
+        Ok(id)
+    }
+
+    /// Queues files for background transcription. Folders are expanded to the
+    /// supported audio files directly inside them.
+    pub fn enqueue(&self, paths: Vec<PathBuf>) -> Result<Vec<TranscriptionJob>> {
+        let mut files = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut entries: Vec<PathBuf> = std::fs::read_dir(&path)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|p| p.is_file() && is_supported_audio(p))
+                    .collect();
+                entries.sort();
+                files.extend(entries);
+            } else if is_supported_audio(&path) {
+                files.push(path);
+            } else {
+                debug!("Skipping unsupported file: {}", path.display());
+            }
+        }
+
+        if files.is_empty() {
+            anyhow::bail!(
+                "No audio files found. Supported formats: {}",
+                SUPPORTED_AUDIO_EXTENSIONS.join(", ")
+            );
+        }
+
+        let now = Utc::now().timestamp();
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        let mut ids = Vec::with_capacity(files.len());
+        for file in &files {
+            tx.execute(
+                "INSERT INTO transcription_jobs (file_path, status, progress, created_at, updated_at)
+                 VALUES (?1, 'queued', 0, ?2, ?2)",
+                params![file.to_string_lossy(), now],
+            )?;
+            ids.push(tx.last_insert_rowid());
+        }
+        tx.commit()?;
+
+        debug!("Queued {} transcription jobs", ids.len());
+        self.wake_worker();
+
+        let mut jobs = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(job) = self.get_job(id)? {
+                self.emit_job_update(&job);
+                jobs.push(job);
+            }
+        }
+        Ok(jobs)
+    }
+
+    pub fn get_jobs(&self) -> Result<Vec<TranscriptionJob>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_jobs ORDER BY id DESC",
+            JOB_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], row_to_job)?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            jobs.push(row?);
+        }
+        Ok(jobs)
+    }
+
+    fn get_job(&self, id: i64) -> Result<Option<TranscriptionJob>> {
+        let conn = self.get_connection()?;
+        let job = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM transcription_jobs WHERE id = ?1",
+                    JOB_COLUMNS
+                ),
+                params![id],
+                row_to_job,
+            )
+            .optional()?;
+        Ok(job)
+    }
+
+    /// Cancels a queued job, or stops a running one after its current chunk
+    pub fn cancel_job(&self, id: i64) -> Result<()> {
+        if *self.running_job.lock().unwrap() == Some(id) {
+            self.cancel_running.store(true, Ordering::SeqCst);
+            return Ok(());
+        }
+
+        let conn = self.get_connection()?;
+        let updated = conn.execute(
+            "UPDATE transcription_jobs SET status = 'cancelled', updated_at = ?1
+             WHERE id = ?2 AND status = 'queued'",
+            params![Utc::now().timestamp(), id],
+        )?;
+        if updated > 0 {
+            if let Some(job) = self.get_job(id)? {
+                self.emit_job_update(&job);
+            }
+        }
+        Ok(())
+    }
+
+    /// Cancels every queued job and stops the running one
+    pub fn cancel_all_jobs(&self) -> Result<()> {
+        if self.running_job.lock().unwrap().is_some() {
+            self.cancel_running.store(true, Ordering::SeqCst);
+        }
+
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare("SELECT id FROM transcription_jobs WHERE status = 'queued'")?;
+        let ids: Vec<i64> = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for id in ids {
+            self.cancel_job(id)?;
+        }
+        Ok(())
+    }
+
+    /// Removes completed, failed and cancelled jobs from the list
+    pub fn clear_finished_jobs(&self) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "DELETE FROM transcription_jobs WHERE status IN ('completed', 'failed', 'cancelled')",
+            [],
+        )?;
+        if let Err(e) = self.app_handle.emit("transcription-jobs-cleared", ()) {
+            error!("Failed to emit transcription-jobs-cleared event: {}", e);
+        }
+        Ok(())
+    }
+
+    fn wake_worker(&self) {
+        let (pending, condvar) = &*self.work_pending;
+        *pending.lock().unwrap() = true;
+        condvar.notify_one();
+    }
+
+    fn run_worker(&self) {
+        // Jobs left running by a previous session start over
+        if let Err(e) = self.requeue_interrupted_jobs() {
+            warn!("Failed to resume interrupted transcription jobs: {}", e);
+        }
+
+        loop {
+            {
+                let (pending, condvar) = &*self.work_pending;
+                let mut pending = pending.lock().unwrap();
+                while !*pending {
+                    pending = condvar.wait(pending).unwrap();
+                }
+                *pending = false;
+            }
+
+            loop {
+                match self.next_queued_job() {
+                    Ok(Some(job)) => self.run_job(job),
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Failed to read transcription job queue: {}", e);
+                        thread::sleep(Duration::from_secs(5));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn requeue_interrupted_jobs(&self) -> Result<()> {
+        let conn = self.get_connection()?;
+        let resumed = conn.execute(
+            "UPDATE transcription_jobs SET status = 'queued', progress = 0, updated_at = ?1
+             WHERE status = 'running'",
+            params![Utc::now().timestamp()],
+        )?;
+        if resumed > 0 {
+            info!("Resuming {} interrupted transcription jobs", resumed);
+        }
+        Ok(())
+    }
+
+    fn next_queued_job(&self) -> Result<Option<TranscriptionJob>> {
+        let conn = self.get_connection()?;
+        let job = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM transcription_jobs WHERE status = 'queued' ORDER BY id LIMIT 1",
+                    JOB_COLUMNS
+                ),
+                [],
+                row_to_job,
+            )
+            .optional()?;
+        Ok(job)
+    }
+
+    fn run_job(&self, job: TranscriptionJob) {
+        debug!("Starting transcription job {}: {}", job.id, job.file_path);
+        *self.running_job.lock().unwrap() = Some(job.id);
+        self.cancel_running.store(false, Ordering::SeqCst);
+        self.update_job(job.id, JobStatus::Running, 0.0, None, None);
+
+        let progress_manager = self.clone();
+        let job_id = job.id;
+        let cancel_running = Arc::clone(&self.cancel_running);
+        let result = tauri::async_runtime::block_on(self.import_file_with(
+            PathBuf::from(&job.file_path),
+            move |progress| {
+                progress_manager.update_job(job_id, JobStatus::Running, progress as f64, None, None)
+            },
+            move || cancel_running.load(Ordering::SeqCst),
+        ));
+
+        match result {
+            Ok(history_id) => {
+                self.update_job(job.id, JobStatus::Completed, 1.0, Some(history_id), None)
+            }
+            Err(e) if e.is::<JobCancelled>() => {
+                info!("Transcription job {} cancelled", job.id);
+                self.update_job(job.id, JobStatus::Cancelled, 0.0, None, None)
+            }
+            Err(e) => {
+                error!("Transcription job {} failed: {}", job.id, e);
+                self.update_job(job.id, JobStatus::Failed, 0.0, None, Some(e.to_string()))
+            }
+        }
+
+        *self.running_job.lock().unwrap() = None;
+    }
+
+    /// Persists a job's state and notifies the main window. Errors are logged, since
+    /// a failed bookkeeping write shouldn't abort the transcription itself.
+    fn update_job(
+        &self,
+        id: i64,
+        status: JobStatus,
+        progress: f64,
+        history_id: Option<i64>,
+        error: Option<String>,
+    ) {
+        let result = self.get_connection().and_then(|conn| {
+            conn.execute(
+                "UPDATE transcription_jobs SET status = ?1, progress = ?2, history_id = ?3, error = ?4, updated_at = ?5
+                 WHERE id = ?6",
+                params![status.as_str(), progress, history_id, error, Utc::now().timestamp(), id],
+            )?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            error!("Failed to update transcription job {}: {}", id, e);
+            return;
+        }
+
+        match self.get_job(id) {
+            Ok(Some(job)) => self.emit_job_update(&job),
+            Ok(None) => {}
+            Err(e) => error!("Failed to read transcription job {}: {}", id, e),
+        }
+    }
+
+    fn emit_job_update(&self, job: &TranscriptionJob) {
+        if let Some(main_window) = self.app_handle.get_webview_window("main") {
+            if let Err(e) = main_window.emit("transcription-job-updated", job) {
+                error!("Failed to emit transcription-job-updated event: {}", e);
+            }
+        }
+    }
+}
+
+fn is_supported_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Decodes the file, splits it at silences and transcribes each chunk, shifting the
+/// chunk timings back onto the file's timeline. Returns the sample count and result.
+fn transcribe_file(
+    tm: &TranscriptionManager,
+    path: &Path,
+    vad_path: &Path,
+    on_progress: impl Fn(f32),
+    is_cancelled: impl Fn() -> bool,
+) -> Result<(usize, TimedTranscription)> {
+    let samples = decode_audio_file(path)?;
+    debug!(
+        "Decoded {}: {:.1}s of audio",
+        path.display(),
+        samples.len() as f64 / WHISPER_SAMPLE_RATE as f64
+    );
+    on_progress(DECODE_PROGRESS);
+
+    let mut vad = SileroVad::new(vad_path, 0.3)?;
+    let chunks = split_on_silence(
+        &samples,
+        MAX_CHUNK_SECONDS * WHISPER_SAMPLE_RATE as usize,
+        |frame| vad.is_voice(frame),
+    )?;
+    debug!("Split into {} speech chunks", chunks.len());
+
+    let chunk_count = chunks.len();
+    let mut texts = Vec::new();
+    let mut segments = Vec::new();
+    let mut language = None;
+    for (index, range) in chunks.into_iter().enumerate() {
+        if is_cancelled() {
+            return Err(JobCancelled.into());
+        }
+
+        // Loading is a no-op when the model is already loaded; it may have been
+        // unloaded after the previous chunk when unloading is set to "immediately"
+        tm.initiate_model_load();
+        let chunk = tm.transcribe_timed(samples[range.clone()].to_vec(), JobPriority::Import)?;
+        let offset = range.start as f32 / WHISPER_SAMPLE_RATE as f32;
+
+        if !chunk.text.is_empty() {
+            language = language.or(chunk.language);
+            texts.push(chunk.text);
+        }
+        segments.extend(chunk.segments.into_iter().map(|s| s.shifted(offset)));
+        on_progress(
+            DECODE_PROGRESS + (1.0 - DECODE_PROGRESS) * (index + 1) as f32 / chunk_count as f32,
+        );
+    }
+
+    if texts.is_empty() {
+        anyhow::bail!("No speech found in {}", path.display());
+    }
+
+    Ok((
+        samples.len(),
+        TimedTranscription {
+            text: texts.join(" "),
+            segments,
+            language,
+        },
+    ))
+}
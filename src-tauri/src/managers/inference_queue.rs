@@ -0,0 +1,312 @@
+use log::{debug, error};
+use serde::Serialize;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// What a transcription job is for. Higher priorities always run first, so the
+/// final pass of a recording never waits behind streaming partials or imports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPriority {
+    /// Live streaming windows shown in the overlay while recording
+    Partial,
+    /// File imports, re-transcriptions of saved audio and long-form dictation chunks
+    Import,
+    /// The transcription a finished recording is waiting on
+    Final,
+}
+
+impl JobPriority {
+    const ALL: [JobPriority; 3] = [
+        JobPriority::Final,
+        JobPriority::Import,
+        JobPriority::Partial,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+type StaleCheck = Box<dyn Fn() -> bool + Send>;
+
+thread_local! {
+    /// Set on the worker thread, so jobs can tell they are already running on it
+    static ON_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Job {
+    priority: JobPriority,
+    seq: u64,
+    enqueued_at: Instant,
+    /// Checked right before the job starts; stale jobs are dropped without running
+    is_stale: Option<StaleCheck>,
+    run: Box<dyn FnOnce() + Send>,
+}
+
+// Highest priority first, then oldest first within a priority
+impl Ord for Job {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Job {}
+
+/// How long jobs of one priority waited in the queue before starting
+#[derive(Clone, Debug, Serialize)]
+pub struct PriorityMetrics {
+    pub priority: JobPriority,
+    /// Jobs that ran
+    pub started: u64,
+    /// Jobs dropped as stale before they started
+    pub cancelled: u64,
+    pub total_wait_ms: u64,
+    pub max_wait_ms: u64,
+    pub last_wait_ms: u64,
+}
+
+impl PriorityMetrics {
+    fn new(priority: JobPriority) -> Self {
+        Self {
+            priority,
+            started: 0,
+            cancelled: 0,
+            total_wait_ms: 0,
+            max_wait_ms: 0,
+            last_wait_ms: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct QueueMetrics {
+    /// Jobs waiting to start
+    pub pending: usize,
+    /// Highest priority first
+    pub priorities: Vec<PriorityMetrics>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: BinaryHeap<Job>,
+    next_seq: u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<QueueState>,
+    available: Condvar,
+    /// Indexed by `JobPriority::index`
+    metrics: Mutex<Vec<PriorityMetrics>>,
+}
+
+/// A single inference worker thread that runs transcription jobs one at a time,
+/// highest priority first
+pub struct InferenceQueue {
+    shared: Arc<Shared>,
+}
+
+impl InferenceQueue {
+    /// Spawns the worker thread
+    pub fn start() -> Self {
+        let mut metrics: Vec<PriorityMetrics> = JobPriority::ALL
+            .into_iter()
+            .map(PriorityMetrics::new)
+            .collect();
+        metrics.sort_by_key(|m| m.priority.index());
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(QueueState::default()),
+            available: Condvar::new(),
+            metrics: Mutex::new(metrics),
+        });
+
+        let worker_shared = Arc::clone(&shared);
+        thread::Builder::new()
+            .name("inference-worker".to_string())
+            .spawn(move || run_worker(&worker_shared))
+            .expect("Failed to spawn inference worker");
+
+        Self { shared }
+    }
+
+    /// Queues `run` on the worker. `is_stale` is checked just before the job would
+    /// start; when it returns true the job is dropped instead.
+    pub fn submit(
+        &self,
+        priority: JobPriority,
+        is_stale: Option<StaleCheck>,
+        run: impl FnOnce() + Send + 'static,
+    ) {
+        let mut state = self.shared.state.lock().unwrap();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.jobs.push(Job {
+            priority,
+            seq,
+            enqueued_at: Instant::now(),
+            is_stale,
+            run: Box::new(run),
+        });
+        self.shared.available.notify_one();
+    }
+
+    /// Whether the caller is a job running on the worker. Such a job must not wait for
+    /// another job, which could only start after it returns.
+    pub fn on_worker() -> bool {
+        ON_WORKER.with(Cell::get)
+    }
+
+    pub fn metrics(&self) -> QueueMetrics {
+        let pending = self.shared.state.lock().unwrap().jobs.len();
+        let metrics = self.shared.metrics.lock().unwrap();
+        QueueMetrics {
+            pending,
+            priorities: JobPriority::ALL
+                .iter()
+                .map(|priority| metrics[priority.index()].clone())
+                .collect(),
+        }
+    }
+}
+
+impl Drop for InferenceQueue {
+    fn drop(&mut self) {
+        // Pending jobs are dropped, so anyone waiting on them sees a closed channel
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.available.notify_all();
+    }
+}
+
+fn run_worker(shared: &Shared) {
+    ON_WORKER.with(|on_worker| on_worker.set(true));
+    loop {
+        let job = {
+            let mut state = shared.state.lock().unwrap();
+            loop {
+                if state.shutdown {
+                    debug!("Inference worker shutting down");
+                    return;
+                }
+                if let Some(job) = state.jobs.pop() {
+                    break job;
+                }
+                state = shared.available.wait(state).unwrap();
+            }
+        };
+
+        let wait_ms = job.enqueued_at.elapsed().as_millis() as u64;
+        let stale = job.is_stale.as_ref().is_some_and(|is_stale| is_stale());
+        {
+            let mut metrics = shared.metrics.lock().unwrap();
+            let entry = &mut metrics[job.priority.index()];
+            if stale {
+                entry.cancelled += 1;
+            } else {
+                entry.started += 1;
+                entry.total_wait_ms += wait_ms;
+                entry.max_wait_ms = entry.max_wait_ms.max(wait_ms);
+                entry.last_wait_ms = wait_ms;
+            }
+        }
+
+        if stale {
+            debug!(
+                "Dropping stale {:?} job after {}ms in queue",
+                job.priority, wait_ms
+            );
+            continue;
+        }
+
+        debug!(
+            "Starting {:?} job after {}ms in queue",
+            job.priority, wait_ms
+        );
+        // Keep the worker alive if a job panics
+        if panic::catch_unwind(AssertUnwindSafe(job.run)).is_err() {
+            error!("{:?} transcription job panicked", job.priority);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_jobs_run_by_priority_and_stale_jobs_are_dropped() {
+        let queue = InferenceQueue::start();
+        let (order_tx, order_rx) = mpsc::channel();
+
+        // Hold the worker so the remaining jobs queue up behind it
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel();
+        queue.submit(JobPriority::Import, None, move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        });
+        started_rx.recv().unwrap();
+
+        let stale = Arc::new(AtomicBool::new(false));
+        let stale_check = Arc::clone(&stale);
+        for (priority, label, check) in [
+            (JobPriority::Partial, "old partial", Some(stale_check)),
+            (JobPriority::Partial, "partial", None),
+            (JobPriority::Import, "import", None),
+            (JobPriority::Final, "final", None),
+        ] {
+            let tx = order_tx.clone();
+            let is_stale =
+                check.map(|flag| Box::new(move || flag.load(AtomicOrdering::SeqCst)) as StaleCheck);
+            queue.submit(priority, is_stale, move || tx.send(label).unwrap());
+        }
+        stale.store(true, AtomicOrdering::SeqCst);
+        assert_eq!(queue.metrics().pending, 4);
+
+        release_tx.send(()).unwrap();
+        let order: Vec<&str> = (0..3).map(|_| order_rx.recv().unwrap()).collect();
+        assert_eq!(order, vec!["final", "import", "partial"]);
+
+        let metrics = queue.metrics();
+        let partial = metrics
+            .priorities
+            .iter()
+            .find(|m| m.priority == JobPriority::Partial)
+            .unwrap();
+        assert_eq!((partial.started, partial.cancelled), (1, 1));
+        assert_eq!(metrics.priorities[0].priority, JobPriority::Final);
+    }
+
+    #[test]
+    fn test_jobs_know_they_run_on_the_worker() {
+        let queue = InferenceQueue::start();
+        let (tx, rx) = mpsc::channel();
+        queue.submit(JobPriority::Final, None, move || {
+            tx.send(InferenceQueue::on_worker()).unwrap()
+        });
+        assert!(rx.recv().unwrap());
+        assert!(!InferenceQueue::on_worker());
+    }
+}
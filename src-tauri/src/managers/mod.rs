@@ -1,5 +1,7 @@
 pub mod audio;
 pub mod history;
+pub mod import;
+pub mod inference_queue;
 pub mod model;
 pub mod profile;
 pub mod tag;
@@ -7,14 +7,15 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
-use tokio::io::AsyncWriteExt;
 use std::path::PathBuf;
 use std::sync::Mutex;
 use std::time::Duration;
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::AsyncWriteExt;
 use tokio_util::sync::CancellationToken;
 
+/// Speech engine a model runs on; see `crate::engines::create_engine` for the implementations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EngineType {
     Whisper,
@@ -398,16 +399,22 @@ impl ModelManager {
                     }
                 }
                 // Emit error event
-                let _ = self.app_handle.emit("model-download-error", &serde_json::json!({
-                    "model_id": model_id,
-                    "error": format!("Failed to connect: {}", e)
-                }));
+                let _ = self.app_handle.emit(
+                    "model-download-error",
+                    &serde_json::json!({
+                        "model_id": model_id,
+                        "error": format!("Failed to connect: {}", e)
+                    }),
+                );
                 return Err(anyhow::anyhow!("Failed to connect: {}", e));
             }
         };
 
         // Handle 416 Range Not Satisfiable - delete partial and start fresh
-        let (response, resume_from) = if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && resume_from > 0 {
+        let (response, resume_from) = if response.status()
+            == reqwest::StatusCode::RANGE_NOT_SATISFIABLE
+            && resume_from > 0
+        {
             info!("Got 416 Range Not Satisfiable, deleting partial file and restarting download");
             // Delete the partial file
             if partial_path.exists() {
@@ -421,10 +428,13 @@ impl ModelManager {
                         model.is_downloading = false;
                     }
                 }
-                let _ = self.app_handle.emit("model-download-error", &serde_json::json!({
-                    "model_id": model_id,
-                    "error": format!("Failed to connect on retry: {}", e)
-                }));
+                let _ = self.app_handle.emit(
+                    "model-download-error",
+                    &serde_json::json!({
+                        "model_id": model_id,
+                        "error": format!("Failed to connect on retry: {}", e)
+                    }),
+                );
                 anyhow::anyhow!("Failed to connect on retry: {}", e)
             })?;
             (fresh_response, 0u64)
@@ -443,10 +453,13 @@ impl ModelManager {
                     model.is_downloading = false;
                 }
             }
-            let _ = self.app_handle.emit("model-download-error", &serde_json::json!({
-                "model_id": model_id,
-                "error": format!("HTTP error: {}", response.status())
-            }));
+            let _ = self.app_handle.emit(
+                "model-download-error",
+                &serde_json::json!({
+                    "model_id": model_id,
+                    "error": format!("HTTP error: {}", response.status())
+                }),
+            );
             return Err(anyhow::anyhow!(
                 "Failed to download model: HTTP {}",
                 response.status()
@@ -462,7 +475,10 @@ impl ModelManager {
 
         // If server didn't return content length, emit a warning but continue
         if total_size == 0 {
-            info!("Warning: Server did not return Content-Length for model {}", model_id);
+            info!(
+                "Warning: Server did not return Content-Length for model {}",
+                model_id
+            );
         }
 
         let mut downloaded = resume_from;
@@ -2,10 +2,75 @@ use anyhow::Result;
 use chrono::Utc;
 use log::{debug, error};
 use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::managers::history::Profile;
+use crate::settings::DecodingParams;
+
+const PROFILE_COLUMNS: &str =
+    "id, name, description, color, icon, custom_instructions, ghostwriter_provider, ghostwriter_model,
+     temperature, top_p, max_tokens, decoding_params, vocabulary, created_at, updated_at";
+
+/// Shared mapping function to convert a `PROFILE_COLUMNS` row to a Profile.
+fn row_to_profile(row: &rusqlite::Row) -> rusqlite::Result<Profile> {
+    Ok(Profile {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        description: row.get("description")?,
+        color: row.get("color")?,
+        icon: row.get("icon")?,
+        custom_instructions: row.get("custom_instructions")?,
+        ghostwriter_provider: row.get("ghostwriter_provider")?,
+        ghostwriter_model: row.get("ghostwriter_model")?,
+        temperature: row.get("temperature")?,
+        top_p: row.get("top_p")?,
+        max_tokens: row.get("max_tokens")?,
+        decoding_params: parse_decoding_params(row.get("decoding_params")?),
+        vocabulary: parse_vocabulary(row.get("vocabulary")?),
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Decoding params are stored as JSON; NULL or unreadable JSON means no overrides
+fn parse_decoding_params(json: Option<String>) -> DecodingParams {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// NULL when nothing is overridden, so untouched profiles keep following the global settings
+fn decoding_params_json(params: &DecodingParams) -> Result<Option<String>> {
+    if *params == DecodingParams::default() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(params)?))
+}
+
+/// Vocabulary is stored as a JSON array; NULL or unreadable JSON means no terms
+fn parse_vocabulary(json: Option<String>) -> Vec<String> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn vocabulary_json(vocabulary: &[String]) -> Result<Option<String>> {
+    if vocabulary.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(vocabulary)?))
+}
+
+/// A raw dictation and the output the profile should produce for it,
+/// sent to the ghostwriter as a few-shot example
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProfileExample {
+    pub id: i64,
+    pub profile_id: i64,
+    pub input: String,
+    pub output: String,
+    pub created_at: i64,
+}
 
 pub struct ProfileManager {
     app_handle: AppHandle,
@@ -30,24 +95,12 @@ impl ProfileManager {
     /// Get all profiles
     pub async fn get_profiles(&self) -> Result<Vec<Profile>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, color, icon, custom_instructions, created_at, updated_at
-             FROM profiles
-             ORDER BY created_at ASC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM profiles ORDER BY created_at ASC",
+            PROFILE_COLUMNS
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(Profile {
-                id: row.get("id")?,
-                name: row.get("name")?,
-                description: row.get("description")?,
-                color: row.get("color")?,
-                icon: row.get("icon")?,
-                custom_instructions: row.get("custom_instructions")?,
-                created_at: row.get("created_at")?,
-                updated_at: row.get("updated_at")?,
-            })
-        })?;
+        let rows = stmt.query_map([], row_to_profile)?;
 
         let mut profiles = Vec::new();
         for row in rows {
@@ -60,29 +113,44 @@ impl ProfileManager {
     /// Get a single profile by ID
     pub async fn get_profile(&self, id: i64) -> Result<Option<Profile>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, color, icon, custom_instructions, created_at, updated_at
-             FROM profiles
-             WHERE id = ?1"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM profiles WHERE id = ?1",
+            PROFILE_COLUMNS
+        ))?;
 
-        let profile = stmt.query_row([id], |row| {
-            Ok(Profile {
-                id: row.get("id")?,
-                name: row.get("name")?,
-                description: row.get("description")?,
-                color: row.get("color")?,
-                icon: row.get("icon")?,
-                custom_instructions: row.get("custom_instructions")?,
-                created_at: row.get("created_at")?,
-                updated_at: row.get("updated_at")?,
-            })
-        }).optional()?;
+        let profile = stmt.query_row([id], row_to_profile).optional()?;
 
         Ok(profile)
     }
 
+    /// Decoding overrides of a profile; blocking, so transcription can call it directly
+    pub fn get_decoding_params(&self, id: i64) -> Result<DecodingParams> {
+        let conn = self.get_connection()?;
+        let json: Option<Option<String>> = conn
+            .query_row(
+                "SELECT decoding_params FROM profiles WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(parse_decoding_params(json.flatten()))
+    }
+
+    /// Vocabulary of a profile; blocking, so transcription can call it directly
+    pub fn get_vocabulary(&self, id: i64) -> Result<Vec<String>> {
+        let conn = self.get_connection()?;
+        let json: Option<Option<String>> = conn
+            .query_row(
+                "SELECT vocabulary FROM profiles WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(parse_vocabulary(json.flatten()))
+    }
+
     /// Create a new profile
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_profile(
         &self,
         name: String,
@@ -90,14 +158,27 @@ impl ProfileManager {
         color: String,
         icon: String,
         custom_instructions: Option<String>,
+        ghostwriter_provider: Option<String>,
+        ghostwriter_model: Option<String>,
+        temperature: Option<f64>,
+        top_p: Option<f64>,
+        max_tokens: Option<i64>,
+        decoding_params: &DecodingParams,
+        vocabulary: &[String],
     ) -> Result<i64> {
         let conn = self.get_connection()?;
         let now = Utc::now().timestamp();
+        let decoding_params = decoding_params_json(decoding_params)?;
+        let vocabulary = vocabulary_json(vocabulary)?;
 
         conn.execute(
-            "INSERT INTO profiles (name, description, color, icon, custom_instructions, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![name, description, color, icon, custom_instructions, now, now],
+            "INSERT INTO profiles (name, description, color, icon, custom_instructions, ghostwriter_provider,
+                                   ghostwriter_model, temperature, top_p, max_tokens, decoding_params,
+                                   vocabulary, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![name, description, color, icon, custom_instructions, ghostwriter_provider,
+                    ghostwriter_model, temperature, top_p, max_tokens, decoding_params, vocabulary,
+                    now, now],
         )?;
 
         let id = conn.last_insert_rowid();
@@ -112,6 +193,7 @@ impl ProfileManager {
     }
 
     /// Update an existing profile
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_profile(
         &self,
         id: i64,
@@ -120,15 +202,41 @@ impl ProfileManager {
         color: String,
         icon: String,
         custom_instructions: Option<String>,
+        ghostwriter_provider: Option<String>,
+        ghostwriter_model: Option<String>,
+        temperature: Option<f64>,
+        top_p: Option<f64>,
+        max_tokens: Option<i64>,
+        decoding_params: &DecodingParams,
+        vocabulary: &[String],
     ) -> Result<()> {
         let conn = self.get_connection()?;
         let now = Utc::now().timestamp();
+        let decoding_params = decoding_params_json(decoding_params)?;
+        let vocabulary = vocabulary_json(vocabulary)?;
 
         conn.execute(
             "UPDATE profiles
-             SET name = ?1, description = ?2, color = ?3, icon = ?4, custom_instructions = ?5, updated_at = ?6
-             WHERE id = ?7",
-            params![name, description, color, icon, custom_instructions, now, id],
+             SET name = ?1, description = ?2, color = ?3, icon = ?4, custom_instructions = ?5,
+                 ghostwriter_provider = ?6, ghostwriter_model = ?7, temperature = ?8, top_p = ?9,
+                 max_tokens = ?10, decoding_params = ?11, vocabulary = ?12, updated_at = ?13
+             WHERE id = ?14",
+            params![
+                name,
+                description,
+                color,
+                icon,
+                custom_instructions,
+                ghostwriter_provider,
+                ghostwriter_model,
+                temperature,
+                top_p,
+                max_tokens,
+                decoding_params,
+                vocabulary,
+                now,
+                id
+            ],
         )?;
 
         debug!("Updated profile: {}", id);
@@ -162,6 +270,12 @@ impl ProfileManager {
             params![id],
         )?;
 
+        // Foreign keys aren't enforced on these connections, so delete examples explicitly
+        conn.execute(
+            "DELETE FROM profile_examples WHERE profile_id = ?1",
+            params![id],
+        )?;
+
         // Delete the profile
         conn.execute("DELETE FROM profiles WHERE id = ?1", params![id])?;
 
@@ -177,27 +291,95 @@ impl ProfileManager {
 
     /// Get profile by name (for quick lookups)
     pub async fn get_profile_by_name(&self, name: &str) -> Result<Option<Profile>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM profiles WHERE name = ?1",
+            PROFILE_COLUMNS
+        ))?;
+
+        let profile = stmt.query_row([name], row_to_profile).optional()?;
+
+        Ok(profile)
+    }
+
+    /// Get a profile's few-shot examples, oldest first
+    pub async fn get_examples(&self, profile_id: i64) -> Result<Vec<ProfileExample>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, color, icon, custom_instructions, created_at, updated_at
-             FROM profiles
-             WHERE name = ?1"
+            "SELECT id, profile_id, input, output, created_at FROM profile_examples
+             WHERE profile_id = ?1 ORDER BY created_at ASC, id ASC",
         )?;
 
-        let profile = stmt.query_row([name], |row| {
-            Ok(Profile {
+        let rows = stmt.query_map([profile_id], |row| {
+            Ok(ProfileExample {
                 id: row.get("id")?,
-                name: row.get("name")?,
-                description: row.get("description")?,
-                color: row.get("color")?,
-                icon: row.get("icon")?,
-                custom_instructions: row.get("custom_instructions")?,
+                profile_id: row.get("profile_id")?,
+                input: row.get("input")?,
+                output: row.get("output")?,
                 created_at: row.get("created_at")?,
-                updated_at: row.get("updated_at")?,
             })
-        }).optional()?;
+        })?;
 
-        Ok(profile)
+        let mut examples = Vec::new();
+        for row in rows {
+            examples.push(row?);
+        }
+
+        Ok(examples)
+    }
+
+    /// Add a few-shot example to a profile
+    pub async fn add_example(&self, profile_id: i64, input: String, output: String) -> Result<i64> {
+        let conn = self.get_connection()?;
+        let now = Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO profile_examples (profile_id, input, output, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![profile_id, input, output, now],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        debug!("Added example {} to profile {}", id, profile_id);
+
+        if let Err(e) = self.app_handle.emit("profiles-updated", ()) {
+            error!("Failed to emit profiles-updated event: {}", e);
+        }
+
+        Ok(id)
+    }
+
+    /// Update the text of a few-shot example
+    pub async fn update_example(&self, id: i64, input: String, output: String) -> Result<()> {
+        let conn = self.get_connection()?;
+        let updated = conn.execute(
+            "UPDATE profile_examples SET input = ?1, output = ?2 WHERE id = ?3",
+            params![input, output, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Example {} not found", id));
+        }
+
+        debug!("Updated example: {}", id);
+
+        if let Err(e) = self.app_handle.emit("profiles-updated", ()) {
+            error!("Failed to emit profiles-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Delete a few-shot example
+    pub async fn delete_example(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute("DELETE FROM profile_examples WHERE id = ?1", params![id])?;
+
+        debug!("Deleted example: {}", id);
+
+        if let Err(e) = self.app_handle.emit("profiles-updated", ()) {
+            error!("Failed to emit profiles-updated event: {}", e);
+        }
+
+        Ok(())
     }
 
     /// Get statistics for a profile (how many transcriptions use it)
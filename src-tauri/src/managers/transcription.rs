@@ -1,24 +1,21 @@
-use crate::audio_toolkit::apply_custom_words;
-use crate::cpu_features;
-use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::{
+    apply_custom_words, build_initial_prompt, split_on_silence, SileroVad, VoiceActivityDetector,
+};
+use crate::engines::{create_engine, EngineOptions, SpeechEngine};
+use crate::language::{detect_language, DetectedLanguage};
+use crate::managers::inference_queue::{InferenceQueue, JobPriority, QueueMetrics};
+use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::profile::ProfileManager;
+use crate::settings::{get_settings, AppSettings, DecodingParams, ModelUnloadTimeout};
 use anyhow::Result;
 use log::{debug, error, info};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
-use transcribe_rs::{
-    engines::{
-        parakeet::{
-            ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
-        },
-        whisper::{WhisperEngine, WhisperInferenceParams},
-    },
-    TranscriptionEngine,
-};
+use tauri::{AppHandle, Emitter, Manager};
 
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
@@ -28,14 +25,185 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
-enum LoadedEngine {
-    Whisper(WhisperEngine),
-    Parakeet(ParakeetEngine),
+/// A recognised word and when it was spoken, in seconds from the start of the audio
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WordTiming {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    /// Recognition confidence from 0 to 1, when the engine reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// A sentence-sized stretch of the transcript with its word timings
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    pub words: Vec<WordTiming>,
+}
+
+impl TranscriptSegment {
+    /// Moves the segment and its words `offset` seconds later, for audio transcribed in chunks
+    pub fn shifted(mut self, offset: f32) -> Self {
+        self.start += offset;
+        self.end += offset;
+        for word in &mut self.words {
+            word.start += offset;
+            word.end += offset;
+        }
+        self
+    }
+}
+
+/// Transcript text together with segment and word timings
+#[derive(Clone, Debug, Default)]
+pub struct TimedTranscription {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    /// Language the audio was dictated in; None for translated transcripts
+    pub language: Option<DetectedLanguage>,
+}
+
+/// Per-call replacements for the language and translation settings
+#[derive(Clone, Debug, Default)]
+pub struct TranscriptionOverrides {
+    /// Language code or "auto"; None uses the selected language setting
+    pub language: Option<String>,
+    pub translate: Option<bool>,
+}
+
+/// Which loaded engine a transcription runs on
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EngineRole {
+    /// The selected model, used for final passes, imports and re-transcriptions
+    Primary,
+    /// The streaming model when one is loaded, otherwise the selected model
+    Streaming,
+}
+
+/// Longest chunk handed to the engine at once (Whisper's context window)
+pub const MAX_CHUNK_SECONDS: usize = 30;
+
+/// Combined size of the two models above which the streaming model is unloaded as soon
+/// as a recording ends, rather than staying loaded until the idle timeout
+const DUAL_MODEL_KEEP_LOADED_MB: u64 = 1024;
+
+/// Word-level runs are split into a new segment after a pause this long (seconds)
+const SEGMENT_PAUSE_SECONDS: f32 = 1.0;
+
+/// Engines without word timestamps only report segment timings, so word timings are
+/// interpolated across the segment in proportion to each word's length. Words get their
+/// own confidence when there is one per word, otherwise the segment's.
+fn interpolate_words(
+    start: f32,
+    end: f32,
+    text: &str,
+    confidence: Option<f32>,
+    word_confidences: &[f32],
+) -> Vec<WordTiming> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|w| w.chars().count()).sum();
+    if total_chars == 0 {
+        return Vec::new();
+    }
+    // Word correction can merge or split words, leaving the confidences misaligned
+    let per_word = word_confidences.len() == words.len();
+
+    let duration = (end - start).max(0.0);
+    let mut elapsed_chars = 0;
+    words
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let word_start = start + duration * elapsed_chars as f32 / total_chars as f32;
+            elapsed_chars += word.chars().count();
+            let word_end = start + duration * elapsed_chars as f32 / total_chars as f32;
+            WordTiming {
+                text: word.to_string(),
+                start: word_start,
+                end: word_end,
+                confidence: if per_word {
+                    Some(word_confidences[i])
+                } else {
+                    confidence
+                },
+            }
+        })
+        .collect()
+}
+
+/// Groups word-level timings into segments, breaking after sentence-ending
+/// punctuation or a long pause.
+fn group_words(words: Vec<WordTiming>) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut current: Vec<WordTiming> = Vec::new();
+
+    for word in words {
+        let paused = current
+            .last()
+            .is_some_and(|last| word.start - last.end >= SEGMENT_PAUSE_SECONDS);
+        if paused {
+            segments.push(segment_from_words(std::mem::take(&mut current)));
+        }
+
+        let ends_sentence = word.text.ends_with(['.', '!', '?']);
+        current.push(word);
+        if ends_sentence {
+            segments.push(segment_from_words(std::mem::take(&mut current)));
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push(segment_from_words(current));
+    }
+    segments
+}
+
+fn segment_from_words(words: Vec<WordTiming>) -> TranscriptSegment {
+    TranscriptSegment {
+        text: words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        start: words.first().map_or(0.0, |w| w.start),
+        end: words.last().map_or(0.0, |w| w.end),
+        words,
+    }
+}
+
+/// Distinct words recognised with less than `threshold` confidence, in transcript order
+/// and without surrounding punctuation. Words without a confidence are never included.
+pub fn low_confidence_words(segments: &[TranscriptSegment], threshold: f32) -> Vec<String> {
+    let mut uncertain: Vec<String> = Vec::new();
+    for word in segments.iter().flat_map(|segment| &segment.words) {
+        if !word
+            .confidence
+            .is_some_and(|confidence| confidence < threshold)
+        {
+            continue;
+        }
+        let text = word.text.trim_matches(|c: char| !c.is_alphanumeric());
+        if !text.is_empty() && !uncertain.iter().any(|w| w.eq_ignore_ascii_case(text)) {
+            uncertain.push(text.to_string());
+        }
+    }
+    uncertain
+}
+
+/// The active profile's decoding params and vocabulary, merged with the global settings
+#[derive(Clone, Debug)]
+struct ProfileOptions {
+    decoding: DecodingParams,
+    vocabulary: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct TranscriptionManager {
-    engine: Arc<Mutex<Option<LoadedEngine>>>,
+    engine: Arc<Mutex<Option<Box<dyn SpeechEngine>>>>,
     model_manager: Arc<ModelManager>,
     app_handle: AppHandle,
     current_model_id: Arc<Mutex<Option<String>>>,
@@ -46,6 +214,13 @@ pub struct TranscriptionManager {
     loading_condvar: Arc<Condvar>,
     /// When true, prevents auto-unload after transcription (used during active recording)
     suppress_unload: Arc<AtomicBool>,
+    /// Runs every transcription on one worker, final passes first
+    queue: Arc<InferenceQueue>,
+    /// Faster model for live streaming windows, loaded alongside `engine` when configured
+    streaming_engine: Arc<Mutex<Option<Box<dyn SpeechEngine>>>>,
+    streaming_model_id: Arc<Mutex<Option<String>>>,
+    /// Resolved once while recording, so streaming windows don't each query the database
+    recording_options: Arc<Mutex<Option<ProfileOptions>>>,
 }
 
 impl TranscriptionManager {
@@ -66,6 +241,10 @@ impl TranscriptionManager {
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
             suppress_unload: Arc::new(AtomicBool::new(false)),
+            queue: Arc::new(InferenceQueue::start()),
+            streaming_engine: Arc::new(Mutex::new(None)),
+            streaming_model_id: Arc::new(Mutex::new(None)),
+            recording_options: Arc::new(Mutex::new(None)),
         };
 
         // Start the idle watcher
@@ -143,22 +322,68 @@ impl TranscriptionManager {
         *self.is_loading.lock().unwrap()
     }
 
-    /// Prevent auto-unload (call during recording to keep model loaded for streaming)
+    /// Prevent auto-unload (call during recording to keep model loaded for streaming).
+    /// Lifting it frees the streaming model right away when both models together are large.
     pub fn set_suppress_unload(&self, suppress: bool) {
         self.suppress_unload.store(suppress, Ordering::SeqCst);
+        if !suppress && self.dual_model_size_mb() > DUAL_MODEL_KEEP_LOADED_MB {
+            debug!(
+                "Both models exceed {}MB, unloading streaming model",
+                DUAL_MODEL_KEEP_LOADED_MB
+            );
+            self.unload_streaming_model();
+        }
     }
 
+    /// Resolves the active profile's decoding params and vocabulary for the recording that
+    /// is starting; transcriptions use them until `clear_recording_options`
+    pub fn cache_recording_options(&self) {
+        self.clear_recording_options();
+        let settings = get_settings(&self.app_handle);
+        let options = ProfileOptions {
+            decoding: self.resolve_decoding_params(&settings),
+            vocabulary: self.resolve_vocabulary(&settings),
+        };
+        *self.recording_options.lock().unwrap() = Some(options);
+    }
+
+    pub fn clear_recording_options(&self) {
+        *self.recording_options.lock().unwrap() = None;
+    }
+
+    /// The separate streaming model configured in `settings`, if any. Choosing the
+    /// selected model again means streaming shares the loaded engine.
+    pub fn streaming_model(settings: &AppSettings) -> Option<String> {
+        settings
+            .streaming_model
+            .clone()
+            .filter(|id| !id.is_empty() && *id != settings.selected_model)
+    }
+
+    /// Combined size of the loaded models, or 0 when no streaming model is loaded
+    fn dual_model_size_mb(&self) -> u64 {
+        let streaming = self.streaming_model_id.lock().unwrap().clone();
+        let Some(streaming) = streaming else {
+            return 0;
+        };
+        [Some(streaming), self.get_current_model()]
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.model_manager.get_model_info(&id))
+            .map(|info| info.size_mb)
+            .sum()
+    }
+
+    /// Unloads the selected model, and the streaming model with it
     pub fn unload_model(&self) -> Result<()> {
         let unload_start = std::time::Instant::now();
         debug!("Starting to unload model");
+        self.unload_streaming_model();
 
         {
             let mut engine = self.engine.lock().unwrap();
             if let Some(ref mut loaded_engine) = *engine {
-                match loaded_engine {
-                    LoadedEngine::Whisper(ref mut whisper) => whisper.unload_model(),
-                    LoadedEngine::Parakeet(ref mut parakeet) => parakeet.unload_model(),
-                }
+                loaded_engine.unload();
             }
             *engine = None; // Drop the engine to free memory
         }
@@ -220,67 +445,19 @@ impl TranscriptionManager {
             return Err(anyhow::anyhow!(error_msg));
         }
 
-        let model_path = self.model_manager.get_model_path(model_id)?;
-
-        // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                // Check CPU capabilities before attempting to load Parakeet model
-                // ONNX Runtime is compiled with AVX/AVX2 instructions and will crash
-                // with STATUS_ILLEGAL_INSTRUCTION on CPUs without these features
-                if !cpu_features::supports_parakeet() {
-                    let error_msg = format!(
-                        "Cannot load Parakeet model: CPU does not support required AVX2 instructions. \
-                         Your processor lacks the AVX/AVX2 features required by ONNX Runtime. \
-                         Please use Whisper models instead (e.g., whisper-small, whisper-medium)."
-                    );
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    return Err(anyhow::anyhow!(error_msg));
-                }
-
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
+        let loaded_engine = match self.open_engine(model_id, &model_info) {
+            Ok(engine) => engine,
+            Err(e) => {
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(e.to_string()),
+                    },
+                );
+                return Err(e);
             }
         };
 
@@ -323,19 +500,85 @@ impl TranscriptionManager {
         Ok(())
     }
 
-    /// Kicks off the model loading in a background thread if it's not already loaded
+    /// Creates the engine registered for the model's type and loads the model into it
+    fn open_engine(&self, model_id: &str, model_info: &ModelInfo) -> Result<Box<dyn SpeechEngine>> {
+        let model_path = self.model_manager.get_model_path(model_id)?;
+
+        let mut loaded_engine = create_engine(&model_info.engine_type);
+        loaded_engine.check_supported()?;
+        loaded_engine.load(&model_path).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to load {} model {}: {}",
+                loaded_engine.name().to_lowercase(),
+                model_id,
+                e
+            )
+        })?;
+        Ok(loaded_engine)
+    }
+
+    /// Loads `model_id` as the streaming model, next to the selected one. Unlike
+    /// `load_model` this emits no model state events, since the model selector only
+    /// tracks the selected model.
+    pub fn load_streaming_model(&self, model_id: &str) -> Result<()> {
+        let load_start = std::time::Instant::now();
+        let model_info = self
+            .model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if !model_info.is_downloaded {
+            return Err(anyhow::anyhow!("Model not downloaded"));
+        }
+
+        let loaded_engine = self.open_engine(model_id, &model_info)?;
+        *self.streaming_engine.lock().unwrap() = Some(loaded_engine);
+        *self.streaming_model_id.lock().unwrap() = Some(model_id.to_string());
+
+        debug!(
+            "Loaded streaming model: {} (took {}ms)",
+            model_id,
+            load_start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    pub fn unload_streaming_model(&self) {
+        let mut engine = self.streaming_engine.lock().unwrap();
+        if let Some(ref mut loaded_engine) = *engine {
+            loaded_engine.unload();
+            debug!("Streaming model unloaded");
+        }
+        *engine = None;
+        *self.streaming_model_id.lock().unwrap() = None;
+    }
+
+    /// Kicks off loading the selected model, and the streaming model if one is
+    /// configured, in a background thread unless they're already loaded
     pub fn initiate_model_load(&self) {
         let mut is_loading = self.is_loading.lock().unwrap();
-        if *is_loading || self.is_model_loaded() {
+        let settings = get_settings(&self.app_handle);
+        if Self::streaming_model(&settings).is_none() {
+            // Streaming was switched off or now matches the selected model
+            self.unload_streaming_model();
+        }
+        let streaming_model = Self::streaming_model(&settings)
+            .filter(|id| self.streaming_model_id.lock().unwrap().as_deref() != Some(id.as_str()));
+        if *is_loading || (self.is_model_loaded() && streaming_model.is_none()) {
             return;
         }
 
         *is_loading = true;
         let self_clone = self.clone();
         thread::spawn(move || {
-            let settings = get_settings(&self_clone.app_handle);
-            if let Err(e) = self_clone.load_model(&settings.selected_model) {
-                error!("Failed to load model: {}", e);
+            if !self_clone.is_model_loaded() {
+                if let Err(e) = self_clone.load_model(&settings.selected_model) {
+                    error!("Failed to load model: {}", e);
+                }
+            }
+            if let Some(model_id) = streaming_model {
+                if let Err(e) = self_clone.load_streaming_model(&model_id) {
+                    error!("Failed to load streaming model {}: {}", model_id, e);
+                }
             }
             let mut is_loading = self_clone.is_loading.lock().unwrap();
             *is_loading = false;
@@ -349,10 +592,268 @@ impl TranscriptionManager {
     }
 
     pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
-        self.transcribe_with_prompt(audio, None)
+        self.run_queued(JobPriority::Final, move |tm| {
+            Ok(tm
+                .run_transcription(
+                    audio,
+                    None,
+                    false,
+                    &TranscriptionOverrides::default(),
+                    EngineRole::Primary,
+                )?
+                .text)
+        })
+    }
+
+    /// Queues a streaming window behind any final or import work. The job is skipped
+    /// if `is_stale` returns true by the time the worker gets to it, and `on_result`
+    /// runs on the worker once it finishes.
+    pub fn transcribe_partial(
+        &self,
+        audio: Vec<f32>,
+        prompt: Option<String>,
+        is_stale: impl Fn() -> bool + Send + 'static,
+        on_result: impl FnOnce(Result<TimedTranscription>) + Send + 'static,
+    ) {
+        let app = self.app_handle.clone();
+        self.queue
+            .submit(JobPriority::Partial, Some(Box::new(is_stale)), move || {
+                let tm = app.state::<Arc<TranscriptionManager>>();
+                on_result(tm.run_transcription(
+                    audio,
+                    prompt,
+                    false,
+                    &TranscriptionOverrides::default(),
+                    EngineRole::Streaming,
+                ));
+            });
+    }
+
+    /// Transcribes `audio` and keeps segment and word timings, for history playback
+    pub fn transcribe_timed(
+        &self,
+        audio: Vec<f32>,
+        priority: JobPriority,
+    ) -> Result<TimedTranscription> {
+        self.run_queued(priority, move |tm| {
+            tm.run_transcription(
+                audio,
+                None,
+                true,
+                &TranscriptionOverrides::default(),
+                EngineRole::Primary,
+            )
+        })
     }
 
-    pub fn transcribe_with_prompt(&self, audio: Vec<f32>, prompt: Option<String>) -> Result<String> {
+    /// Transcribes a whole recording in chunks of at most `MAX_CHUNK_SECONDS`, split at
+    /// silences, prompting each chunk with the text of the one before. Runs as one final
+    /// job so no streaming window lands between the chunks.
+    pub fn transcribe_accurate(&self, audio: Vec<f32>) -> Result<TimedTranscription> {
+        let vad_path = self
+            .app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))?;
+
+        self.run_queued(JobPriority::Final, move |tm| {
+            let mut vad = SileroVad::new(&vad_path, 0.3)?;
+            let chunks = split_on_silence(
+                &audio,
+                MAX_CHUNK_SECONDS * WHISPER_SAMPLE_RATE as usize,
+                |frame| vad.is_voice(frame),
+            )?;
+            debug!("Accurate final pass over {} chunks", chunks.len());
+
+            let mut texts: Vec<String> = Vec::new();
+            let mut segments = Vec::new();
+            let mut language = None;
+            for range in chunks {
+                // The model may have been unloaded after the previous chunk when unloading
+                // is set to "immediately"
+                tm.initiate_model_load();
+                let chunk = tm.run_transcription(
+                    audio[range.clone()].to_vec(),
+                    texts.last().cloned(),
+                    true,
+                    &TranscriptionOverrides::default(),
+                    EngineRole::Primary,
+                )?;
+                let offset = range.start as f32 / WHISPER_SAMPLE_RATE as f32;
+
+                if !chunk.text.is_empty() {
+                    language = language.or(chunk.language);
+                    texts.push(chunk.text);
+                }
+                segments.extend(chunk.segments.into_iter().map(|s| s.shifted(offset)));
+            }
+
+            Ok(TimedTranscription {
+                text: texts.join(" "),
+                segments,
+                language,
+            })
+        })
+    }
+
+    /// Queues one chunk of a long-form recording without waiting for it. `prompt` runs
+    /// when the job starts, so it can use the text of chunks queued before this one.
+    pub fn transcribe_chunk(
+        &self,
+        audio: Vec<f32>,
+        prompt: impl FnOnce() -> Option<String> + Send + 'static,
+        on_result: impl FnOnce(Result<TimedTranscription>) + Send + 'static,
+    ) {
+        let app = self.app_handle.clone();
+        self.queue.submit(JobPriority::Import, None, move || {
+            let tm = app.state::<Arc<TranscriptionManager>>();
+            tm.initiate_model_load();
+            on_result(tm.run_transcription(
+                audio,
+                prompt(),
+                true,
+                &TranscriptionOverrides::default(),
+                EngineRole::Primary,
+            ));
+        });
+    }
+
+    /// Transcribes `audio` with `model_id`, then switches back to the model that was
+    /// loaded before (or unloads it again if none was)
+    pub fn transcribe_with_model(
+        &self,
+        model_id: &str,
+        audio: Vec<f32>,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String> {
+        let model_id = model_id.to_string();
+        let overrides = overrides.clone();
+        // The whole switch runs as one job so no other work lands on the borrowed model
+        self.run_queued(JobPriority::Import, move |tm| {
+            let previous_model = tm.get_current_model();
+            let switch_model = previous_model.as_deref() != Some(model_id.as_str());
+            if switch_model {
+                tm.load_model(&model_id)?;
+            }
+
+            let result = tm
+                .run_transcription(audio, None, false, &overrides, EngineRole::Primary)
+                .map(|transcription| transcription.text);
+
+            if switch_model {
+                let restored = match &previous_model {
+                    Some(previous) => tm.load_model(previous),
+                    None => tm.unload_model(),
+                };
+                if let Err(e) = restored {
+                    error!("Failed to restore model after re-transcription: {}", e);
+                }
+            }
+
+            result
+        })
+    }
+
+    /// Wait times and cancellations of the inference queue
+    pub fn queue_metrics(&self) -> QueueMetrics {
+        self.queue.metrics()
+    }
+
+    /// Runs `job` on the inference worker and blocks until it finishes, or runs it directly
+    /// when already on the worker. The job gets the managed instance, since clones of the
+    /// manager shut down its idle watcher.
+    fn run_queued<T: Send + 'static>(
+        &self,
+        priority: JobPriority,
+        job: impl FnOnce(&TranscriptionManager) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        // Queued from inside a job it would wait on itself, so it runs in place
+        if InferenceQueue::on_worker() {
+            let tm = self.app_handle.state::<Arc<TranscriptionManager>>();
+            return job(&tm);
+        }
+        let (tx, rx) = mpsc::channel();
+        let app = self.app_handle.clone();
+        self.queue.submit(priority, None, move || {
+            let tm = app.state::<Arc<TranscriptionManager>>();
+            let _ = tx.send(job(&tm));
+        });
+        rx.recv()
+            .map_err(|_| anyhow::anyhow!("Transcription was cancelled"))?
+    }
+
+    /// Global decoding settings with the active profile's overrides applied, as cached for
+    /// the current recording if there is one
+    fn resolve_decoding_params(&self, settings: &AppSettings) -> DecodingParams {
+        if let Some(options) = &*self.recording_options.lock().unwrap() {
+            return options.decoding.clone();
+        }
+        let profile_params = settings.active_profile_id.and_then(|id| {
+            let pm = self.app_handle.try_state::<Arc<ProfileManager>>()?;
+            pm.get_decoding_params(id)
+                .map_err(|e| error!("Failed to load decoding params for profile {}: {}", id, e))
+                .ok()
+        });
+        profile_params
+            .unwrap_or_default()
+            .with_fallback(&settings.decoding_params)
+    }
+
+    /// Terms to bias the decoder towards: the active profile's vocabulary first, then
+    /// the global custom words, without case-insensitive duplicates
+    fn resolve_vocabulary(&self, settings: &AppSettings) -> Vec<String> {
+        if let Some(options) = &*self.recording_options.lock().unwrap() {
+            return options.vocabulary.clone();
+        }
+        let profile_terms = settings.active_profile_id.and_then(|id| {
+            let pm = self.app_handle.try_state::<Arc<ProfileManager>>()?;
+            pm.get_vocabulary(id)
+                .map_err(|e| error!("Failed to load vocabulary for profile {}: {}", id, e))
+                .ok()
+        });
+        let mut vocabulary: Vec<String> = Vec::new();
+        let terms = profile_terms.unwrap_or_default();
+        for term in terms
+            .into_iter()
+            .chain(settings.custom_words.iter().cloned())
+        {
+            if !vocabulary.iter().any(|t| t.eq_ignore_ascii_case(&term)) {
+                vocabulary.push(term);
+            }
+        }
+        vocabulary
+    }
+
+    /// Language and translate options for a run: per-call overrides first, then the
+    /// active profile and global decoding params, then the language and translate settings
+    fn resolve_language(
+        settings: &AppSettings,
+        decoding: &DecodingParams,
+        overrides: &TranscriptionOverrides,
+    ) -> (String, bool) {
+        let language = overrides
+            .language
+            .clone()
+            .or_else(|| decoding.language.clone())
+            .unwrap_or_else(|| settings.selected_language.clone());
+        let translate = overrides
+            .translate
+            .or(decoding.translate)
+            .unwrap_or(settings.translate_to_english);
+        (language, translate)
+    }
+
+    fn run_transcription(
+        &self,
+        audio: Vec<f32>,
+        prompt: Option<String>,
+        with_timestamps: bool,
+        overrides: &TranscriptionOverrides,
+        role: EngineRole,
+    ) -> Result<TimedTranscription> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -368,7 +869,7 @@ impl TranscriptionManager {
 
         if audio.is_empty() {
             debug!("Empty audio vector");
-            return Ok(String::new());
+            return Ok(TimedTranscription::default());
         }
 
         // Check if model is loaded, if not try to load it
@@ -387,77 +888,134 @@ impl TranscriptionManager {
 
         // Get current settings for configuration
         let settings = get_settings(&self.app_handle);
+        let decoding = self.resolve_decoding_params(&settings);
+        let (mut language, translate) = Self::resolve_language(&settings, &decoding, overrides);
 
-        if prompt.is_some() {
-            debug!("Using initial_prompt for context ({} chars)", prompt.as_ref().unwrap().len());
+        // Auto-detection limited to one language is just that language
+        let allowed = &settings.allowed_languages;
+        if language == "auto" && allowed.len() == 1 {
+            language = allowed[0].clone();
         }
+        let forced_language = (language != "auto").then_some(language);
 
-        // Perform transcription with the appropriate engine
-        let result = {
-            let mut engine_guard = self.engine.lock().unwrap();
-            let engine = engine_guard.as_mut().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Model failed to load after auto-load attempt. Please check your model settings."
-                )
-            })?;
-
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    // Performance-optimized parameters
-                    let params = WhisperInferenceParams {
-                        language: if settings.selected_language == "auto" {
-                            None
-                        } else {
-                            Some(settings.selected_language.clone())
-                        },
-                        translate: settings.translate_to_english,
-                        initial_prompt: prompt,
-                        // Speed optimizations
-                        suppress_blank: true,              // Skip blank segments for speed
-                        suppress_non_speech_tokens: true,  // Skip non-speech tokens
-                        print_special: false,              // Don't print special tokens
-                        print_progress: false,             // No progress output for speed
-                        print_realtime: false,             // No real-time output
-                        print_timestamps: false,           // Skip timestamps for speed
-                        ..Default::default()
-                    };
-
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
-                }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    // Parakeet doesn't support initial_prompt — ignore it
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                        ..Default::default()
-                    };
-
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
+        // Perform transcription with the loaded engine; streaming windows use the streaming
+        // model when one is loaded. Engines with word timestamps report one timestamped
+        // segment per word.
+        let (result, word_granularity) = {
+            let mut streaming_guard =
+                (role == EngineRole::Streaming).then(|| self.streaming_engine.lock().unwrap());
+            let mut engine_guard;
+            let engine = match streaming_guard.as_mut().and_then(|guard| guard.as_mut()) {
+                Some(engine) => engine,
+                None => {
+                    engine_guard = self.engine.lock().unwrap();
+                    engine_guard.as_mut().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Model failed to load after auto-load attempt. Please check your model settings."
+                        )
+                    })?
                 }
+            };
+            let capabilities = engine.capabilities();
+
+            // The committed-text context, led by the custom vocabulary
+            let prompt = if capabilities.supports_prompt {
+                build_initial_prompt(&self.resolve_vocabulary(&settings), prompt.as_deref())
+            } else {
+                prompt
+            };
+            if let Some(prompt) = &prompt {
+                debug!("Using initial_prompt ({} chars)", prompt.len());
             }
+
+            let options = EngineOptions {
+                language: forced_language.clone(),
+                allowed_languages: allowed.clone(),
+                translate,
+                prompt,
+                timestamps: with_timestamps,
+                decoding,
+            };
+            let result = engine.transcribe(audio, &options)?;
+            (result, with_timestamps && capabilities.word_timestamps)
         };
 
         // Apply word correction if custom words are configured
-        let corrected_result = if !settings.custom_words.is_empty() {
-            apply_custom_words(
-                &result.text,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            )
+        let correct = |text: &str| {
+            if settings.custom_words.is_empty() {
+                text.to_string()
+            } else {
+                apply_custom_words(
+                    text,
+                    &settings.custom_words,
+                    settings.word_correction_threshold,
+                )
+            }
+        };
+        let corrected_result = correct(&result.text);
+
+        // Engines without language identification fall back to guessing from the text
+        let language = if translate {
+            None
         } else {
-            result.text
+            result
+                .language
+                .or_else(|| {
+                    forced_language.map(|code| DetectedLanguage {
+                        code,
+                        probability: None,
+                    })
+                })
+                .or_else(|| detect_language(&corrected_result, allowed))
         };
 
-        let et = std::time::Instant::now();
-        let translation_note = if settings.translate_to_english {
-            " (translated)"
+        let segments = if with_timestamps {
+            let raw_segments = result.segments;
+            if word_granularity {
+                let words = raw_segments
+                    .iter()
+                    .filter_map(|segment| {
+                        let text = correct(segment.text.trim());
+                        (!text.is_empty()).then_some(WordTiming {
+                            text,
+                            start: segment.start,
+                            end: segment.end,
+                            confidence: segment.confidence,
+                        })
+                    })
+                    .collect();
+                group_words(words)
+            } else {
+                raw_segments
+                    .iter()
+                    .filter_map(|segment| {
+                        let text = correct(segment.text.trim());
+                        (!text.is_empty()).then(|| TranscriptSegment {
+                            words: interpolate_words(
+                                segment.start,
+                                segment.end,
+                                &text,
+                                segment.confidence,
+                                &segment.word_confidences,
+                            ),
+                            text,
+                            start: segment.start,
+                            end: segment.end,
+                        })
+                    })
+                    .collect()
+            }
         } else {
-            ""
+            Vec::new()
         };
-        info!("Transcription took {}ms{}", (et - st).as_millis(), translation_note);
+
+        let et = std::time::Instant::now();
+        let translation_note = if translate { " (translated)" } else { "" };
+        info!(
+            "Transcription took {}ms{}",
+            (et - st).as_millis(),
+            translation_note
+        );
 
         // Check if we should immediately unload the model after transcription
         // Skip if suppress_unload is set (active recording session needs the model)
@@ -470,9 +1028,12 @@ impl TranscriptionManager {
             }
         }
 
-        Ok(corrected_result.trim().to_string())
+        Ok(TimedTranscription {
+            text: corrected_result.trim().to_string(),
+            segments,
+            language,
+        })
     }
-
 }
 
 impl Drop for TranscriptionManager {
@@ -492,3 +1053,107 @@ impl Drop for TranscriptionManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f32, end: f32) -> WordTiming {
+        WordTiming {
+            text: text.to_string(),
+            start,
+            end,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_interpolate_words_spans_segment() {
+        let words = interpolate_words(2.0, 4.0, "hi there", Some(0.4), &[]);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "hi");
+        assert_eq!(words[0].start, 2.0);
+        assert!((words[0].end - 2.5714).abs() < 0.001);
+        assert_eq!(words[1].start, words[0].end);
+        assert_eq!(words[1].end, 4.0);
+        assert_eq!(words[1].confidence, Some(0.4));
+        assert!(interpolate_words(0.0, 1.0, "   ", None, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_interpolate_words_uses_word_confidences() {
+        let words = interpolate_words(0.0, 1.0, "hi there", Some(0.6), &[0.9, 0.3]);
+        assert_eq!(words[0].confidence, Some(0.9));
+        assert_eq!(words[1].confidence, Some(0.3));
+
+        // Misaligned after word correction, so the segment confidence is used
+        let words = interpolate_words(0.0, 1.0, "hi there", Some(0.6), &[0.9]);
+        assert!(words.iter().all(|w| w.confidence == Some(0.6)));
+    }
+
+    #[test]
+    fn test_group_words_breaks_on_sentences_and_pauses() {
+        let segments = group_words(vec![
+            word("Hello", 0.0, 0.4),
+            word("world.", 0.5, 0.9),
+            word("Next", 1.0, 1.2),
+            word("part", 1.3, 1.6),
+            word("later", 3.0, 3.4),
+        ]);
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Hello world.", "Next part", "later"]);
+        assert_eq!((segments[1].start, segments[1].end), (1.0, 1.6));
+        assert_eq!(segments[2].words.len(), 1);
+    }
+
+    #[test]
+    fn test_low_confidence_words() {
+        let mut unsure = word("Kubernetes,", 0.0, 0.5);
+        unsure.confidence = Some(0.3);
+        let mut sure = word("deploy", 0.6, 0.9);
+        sure.confidence = Some(0.9);
+        let mut repeated = word("kubernetes", 1.0, 1.4);
+        repeated.confidence = Some(0.2);
+        let segments = group_words(vec![unsure, sure, word("today.", 1.0, 1.2), repeated]);
+
+        assert_eq!(low_confidence_words(&segments, 0.5), vec!["Kubernetes"]);
+        assert!(low_confidence_words(&segments, 0.1).is_empty());
+    }
+
+    #[test]
+    fn test_profile_decoding_params_fall_back_to_globals() {
+        let global = DecodingParams {
+            beam_size: Some(5),
+            no_speech_threshold: Some(0.6),
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        let profile = DecodingParams {
+            no_speech_threshold: Some(0.3),
+            language: Some("de".to_string()),
+            ..Default::default()
+        };
+
+        let resolved = profile.with_fallback(&global);
+        assert_eq!(resolved.beam_size, Some(5));
+        assert_eq!(resolved.no_speech_threshold, Some(0.3));
+        assert_eq!(resolved.language.as_deref(), Some("de"));
+        assert_eq!(resolved.translate, None);
+    }
+
+    #[test]
+    fn test_streaming_model_must_differ_from_selected() {
+        let mut settings = crate::settings::get_default_settings();
+        settings.selected_model = "turbo".to_string();
+        assert_eq!(TranscriptionManager::streaming_model(&settings), None);
+
+        settings.streaming_model = Some("turbo".to_string());
+        assert_eq!(TranscriptionManager::streaming_model(&settings), None);
+
+        settings.streaming_model = Some("small".to_string());
+        assert_eq!(
+            TranscriptionManager::streaming_model(&settings).as_deref(),
+            Some("small")
+        );
+    }
+}
@@ -8,15 +8,15 @@ use tauri_plugin_store::StoreExt;
 // Keyring constants for secure API key storage
 const KEYRING_SERVICE: &str = "com.leadr.leadrscribe";
 const KEYRING_USER: &str = "openrouter_api_key";
+const KEYRING_USER_OPENAI_COMPATIBLE: &str = "openai_compatible_api_key";
 
 // Keyring helper functions for secure API key storage
-fn get_keyring_entry() -> Result<Entry, String> {
-    Entry::new(KEYRING_SERVICE, KEYRING_USER)
-        .map_err(|e| format!("Failed to access keyring: {}", e))
+fn get_keyring_entry(user: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, user).map_err(|e| format!("Failed to access keyring: {}", e))
 }
 
-pub fn get_openrouter_api_key() -> Option<String> {
-    match get_keyring_entry() {
+fn get_keyring_secret(user: &str) -> Option<String> {
+    match get_keyring_entry(user) {
         Ok(entry) => match entry.get_password() {
             Ok(key) => Some(key),
             Err(e) => {
@@ -31,18 +31,44 @@ pub fn get_openrouter_api_key() -> Option<String> {
     }
 }
 
-pub fn set_openrouter_api_key(key: &str) -> Result<(), String> {
-    let entry = get_keyring_entry()?;
-    entry.set_password(key)
+fn set_keyring_secret(user: &str, key: &str) -> Result<(), String> {
+    let entry = get_keyring_entry(user)?;
+    entry
+        .set_password(key)
         .map_err(|e| format!("Failed to save API key: {}", e))
 }
 
-pub fn delete_openrouter_api_key() -> Result<(), String> {
-    let entry = get_keyring_entry()?;
-    entry.delete_credential()
+fn delete_keyring_secret(user: &str) -> Result<(), String> {
+    let entry = get_keyring_entry(user)?;
+    entry
+        .delete_credential()
         .map_err(|e| format!("Failed to delete API key: {}", e))
 }
 
+pub fn get_openrouter_api_key() -> Option<String> {
+    get_keyring_secret(KEYRING_USER)
+}
+
+pub fn set_openrouter_api_key(key: &str) -> Result<(), String> {
+    set_keyring_secret(KEYRING_USER, key)
+}
+
+pub fn delete_openrouter_api_key() -> Result<(), String> {
+    delete_keyring_secret(KEYRING_USER)
+}
+
+pub fn get_openai_compatible_api_key() -> Option<String> {
+    get_keyring_secret(KEYRING_USER_OPENAI_COMPATIBLE)
+}
+
+pub fn set_openai_compatible_api_key(key: &str) -> Result<(), String> {
+    set_keyring_secret(KEYRING_USER_OPENAI_COMPATIBLE, key)
+}
+
+pub fn delete_openai_compatible_api_key() -> Result<(), String> {
+    delete_keyring_secret(KEYRING_USER_OPENAI_COMPATIBLE)
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ShortcutBinding {
     pub id: String,
@@ -146,6 +172,8 @@ pub enum SoundTheme {
 pub enum OutputMode {
     Transcript,
     Ghostwriter,
+    /// Ghostwriter with the selected text (or clipboard) as the message being replied to
+    Reply,
 }
 
 impl Default for OutputMode {
@@ -154,6 +182,34 @@ impl Default for OutputMode {
     }
 }
 
+/// Which chat-completions backend the ghostwriter sends rewrites to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GhostwriterProviderKind {
+    #[default]
+    #[serde(rename = "openrouter")]
+    OpenRouter,
+    /// Any server exposing the OpenAI chat completions API (Ollama, llama.cpp, LM Studio, ...)
+    #[serde(rename = "openai_compatible")]
+    OpenAiCompatible,
+}
+
+impl GhostwriterProviderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GhostwriterProviderKind::OpenRouter => "openrouter",
+            GhostwriterProviderKind::OpenAiCompatible => "openai_compatible",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "openrouter" => Some(GhostwriterProviderKind::OpenRouter),
+            "openai_compatible" => Some(GhostwriterProviderKind::OpenAiCompatible),
+            _ => None,
+        }
+    }
+}
+
 impl SoundTheme {
     fn as_str(&self) -> &'static str {
         match self {
@@ -172,6 +228,74 @@ impl SoundTheme {
     }
 }
 
+/// Whisper decoding options. Every field is optional: a profile's values override the
+/// global ones, and anything left unset falls back to the engine defaults (or, for
+/// language and translate, to the selected language and translate settings).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DecodingParams {
+    /// Beams kept during beam search; 1 means greedy decoding, None the engine default
+    pub beam_size: Option<u32>,
+    /// Candidates sampled per temperature when decoding greedily; set alone, it selects
+    /// greedy decoding
+    pub best_of: Option<u32>,
+    pub temperature: Option<f32>,
+    /// Added to the temperature on each fallback retry after a failed decode
+    pub temperature_increment: Option<f32>,
+    /// Segments with a no-speech probability above this are dropped
+    pub no_speech_threshold: Option<f32>,
+    /// Language code or "auto"
+    pub language: Option<String>,
+    pub translate: Option<bool>,
+    pub threads: Option<u32>,
+}
+
+impl DecodingParams {
+    /// Fills every unset field from `fallback`
+    pub fn with_fallback(self, fallback: &DecodingParams) -> DecodingParams {
+        DecodingParams {
+            beam_size: self.beam_size.or(fallback.beam_size),
+            best_of: self.best_of.or(fallback.best_of),
+            temperature: self.temperature.or(fallback.temperature),
+            temperature_increment: self
+                .temperature_increment
+                .or(fallback.temperature_increment),
+            no_speech_threshold: self.no_speech_threshold.or(fallback.no_speech_threshold),
+            language: self.language.or_else(|| fallback.language.clone()),
+            translate: self.translate.or(fallback.translate),
+            threads: self.threads.or(fallback.threads),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.beam_size.is_some_and(|n| !(1..=16).contains(&n)) {
+            return Err("Beam size must be between 1 and 16".to_string());
+        }
+        if self.best_of.is_some_and(|n| !(1..=16).contains(&n)) {
+            return Err("Best of must be between 1 and 16".to_string());
+        }
+        if self.temperature.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+            return Err("Temperature must be between 0 and 1".to_string());
+        }
+        if self
+            .temperature_increment
+            .is_some_and(|t| !(0.0..=1.0).contains(&t))
+        {
+            return Err("Temperature increment must be between 0 and 1".to_string());
+        }
+        if self
+            .no_speech_threshold
+            .is_some_and(|t| !(0.0..=1.0).contains(&t))
+        {
+            return Err("No-speech threshold must be between 0 and 1".to_string());
+        }
+        if self.threads.is_some_and(|n| n == 0) {
+            return Err("Thread count must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
 /* still handy for composing the initial JSON in the store ------------- */
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppSettings {
@@ -188,6 +312,17 @@ pub struct AppSettings {
     pub autostart_enabled: bool,
     #[serde(default = "default_model")]
     pub selected_model: String,
+    /// Faster model for live streaming; None streams with the selected model
+    #[serde(default)]
+    pub streaming_model: Option<String>,
+    /// Re-transcribe the whole recording in silence-split chunks instead of pasting
+    /// the streaming text
+    #[serde(default)]
+    pub accurate_final_pass: bool,
+    /// Record to a temporary file and transcribe in chunks at pauses, so long sessions
+    /// don't hold the whole recording in memory
+    #[serde(default)]
+    pub long_form_dictation: bool,
     #[serde(default = "default_always_on_microphone")]
     pub always_on_microphone: bool,
     #[serde(default)]
@@ -198,6 +333,9 @@ pub struct AppSettings {
     pub translate_to_english: bool,
     #[serde(default = "default_selected_language")]
     pub selected_language: String,
+    /// Languages auto-detection may pick from; empty allows every language
+    #[serde(default)]
+    pub allowed_languages: Vec<String>,
     #[serde(default = "default_overlay_position")]
     pub overlay_position: OverlayPosition,
     #[serde(default = "default_debug_mode")]
@@ -222,8 +360,43 @@ pub struct AppSettings {
     pub openrouter_api_key: Option<String>,
     #[serde(default = "default_openrouter_model")]
     pub openrouter_model: String,
+    /// OpenRouter models tried in order when `openrouter_model` keeps failing
+    #[serde(default)]
+    pub openrouter_fallback_models: Vec<String>,
     #[serde(default = "default_custom_instructions")]
     pub custom_instructions: String,
+    /// Substituted for `{user_name}` in custom instructions
+    #[serde(default)]
+    pub user_name: String,
+    #[serde(default)]
+    pub ghostwriter_provider: GhostwriterProviderKind,
+    #[serde(default = "default_openai_compatible_base_url")]
+    pub openai_compatible_base_url: String,
+    #[serde(default)]
+    pub openai_compatible_api_key: Option<String>,
+    #[serde(default = "default_openai_compatible_model")]
+    pub openai_compatible_model: String,
+    /// Models tried in order when `openai_compatible_model` keeps failing
+    #[serde(default)]
+    pub openai_compatible_fallback_models: Vec<String>,
+    #[serde(default = "default_ghostwriter_streaming")]
+    pub ghostwriter_streaming: bool,
+    #[serde(default = "default_ghostwriter_max_retries")]
+    pub ghostwriter_max_retries: u32,
+    #[serde(default = "default_ghostwriter_guardrails")]
+    pub ghostwriter_guardrails: bool,
+    /// Words recognised with less confidence than this are highlighted
+    #[serde(default = "default_low_confidence_threshold")]
+    pub low_confidence_threshold: f64,
+    /// Ask the ghostwriter to double-check low-confidence words
+    #[serde(default)]
+    pub ghostwriter_verify_hints: bool,
+    /// Monthly ghostwriter spend cap in USD; None means no cap
+    #[serde(default)]
+    pub ghostwriter_monthly_budget: Option<f64>,
+    /// Decoding defaults; the active profile can override each of them
+    #[serde(default)]
+    pub decoding_params: DecodingParams,
     #[serde(default)]
     pub active_profile_id: Option<i64>,
     #[serde(default = "default_theme_mode")]
@@ -286,7 +459,33 @@ fn default_openrouter_model() -> String {
 }
 
 fn default_custom_instructions() -> String {
-    "Improve grammar, spelling, clarity, and flow while preserving the original meaning and tone.".to_string()
+    "Improve grammar, spelling, clarity, and flow while preserving the original meaning and tone."
+        .to_string()
+}
+
+fn default_openai_compatible_base_url() -> String {
+    // Ollama's OpenAI-compatible endpoint
+    "http://localhost:11434/v1".to_string()
+}
+
+fn default_openai_compatible_model() -> String {
+    "llama3.2".to_string()
+}
+
+fn default_ghostwriter_streaming() -> bool {
+    true
+}
+
+fn default_ghostwriter_max_retries() -> u32 {
+    2
+}
+
+fn default_ghostwriter_guardrails() -> bool {
+    true
+}
+
+fn default_low_confidence_threshold() -> f64 {
+    0.5
 }
 
 fn default_theme_mode() -> String {
@@ -305,6 +504,11 @@ pub fn get_default_settings() -> AppSettings {
     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     let default_shortcut = "alt+space";
 
+    #[cfg(target_os = "macos")]
+    let default_transform_shortcut = "option+shift+space";
+    #[cfg(not(target_os = "macos"))]
+    let default_transform_shortcut = "ctrl+shift+space";
+
     let mut bindings = HashMap::new();
     bindings.insert(
         "transcribe".to_string(),
@@ -316,6 +520,16 @@ pub fn get_default_settings() -> AppSettings {
             current_binding: default_shortcut.to_string(),
         },
     );
+    bindings.insert(
+        "transform_selection".to_string(),
+        ShortcutBinding {
+            id: "transform_selection".to_string(),
+            name: "Transform Selection".to_string(),
+            description: "Rewrites the selected text using a spoken instruction.".to_string(),
+            default_binding: default_transform_shortcut.to_string(),
+            current_binding: default_transform_shortcut.to_string(),
+        },
+    );
 
     AppSettings {
         bindings,
@@ -326,11 +540,15 @@ pub fn get_default_settings() -> AppSettings {
         start_hidden: default_start_hidden(),
         autostart_enabled: default_autostart_enabled(),
         selected_model: "".to_string(),
+        streaming_model: None,
+        accurate_final_pass: false,
+        long_form_dictation: false,
         always_on_microphone: false,
         selected_microphone: None,
         selected_output_device: None,
         translate_to_english: false,
         selected_language: "auto".to_string(),
+        allowed_languages: Vec::new(),
         overlay_position: OverlayPosition::Bottom,
         debug_mode: false,
         custom_words: Vec::new(),
@@ -343,7 +561,21 @@ pub fn get_default_settings() -> AppSettings {
         output_mode: OutputMode::default(),
         openrouter_api_key: None,
         openrouter_model: default_openrouter_model(),
+        openrouter_fallback_models: Vec::new(),
         custom_instructions: default_custom_instructions(),
+        user_name: String::new(),
+        ghostwriter_provider: GhostwriterProviderKind::default(),
+        openai_compatible_base_url: default_openai_compatible_base_url(),
+        openai_compatible_api_key: None,
+        openai_compatible_model: default_openai_compatible_model(),
+        openai_compatible_fallback_models: Vec::new(),
+        ghostwriter_streaming: default_ghostwriter_streaming(),
+        ghostwriter_max_retries: default_ghostwriter_max_retries(),
+        ghostwriter_guardrails: default_ghostwriter_guardrails(),
+        low_confidence_threshold: default_low_confidence_threshold(),
+        ghostwriter_verify_hints: false,
+        ghostwriter_monthly_budget: None,
+        decoding_params: DecodingParams::default(),
         active_profile_id: None,
         theme_mode: default_theme_mode(),
     }
@@ -358,9 +590,22 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
     let settings = if let Some(settings_value) = store.get("settings") {
         // Parse the entire settings object
         match serde_json::from_value::<AppSettings>(settings_value) {
-            Ok(settings) => {
+            Ok(mut settings) => {
                 debug!("Found existing settings: {:?}", settings);
 
+                // Bindings added in newer versions aren't in older stores
+                let mut added_binding = false;
+                for (id, binding) in get_default_settings().bindings {
+                    if !settings.bindings.contains_key(&id) {
+                        debug!("Adding missing default binding '{}'", id);
+                        settings.bindings.insert(id, binding);
+                        added_binding = true;
+                    }
+                }
+                if added_binding {
+                    store.set("settings", serde_json::to_value(&settings).unwrap());
+                }
+
                 settings
             }
             Err(e) => {
@@ -0,0 +1,278 @@
+use serde::Serialize;
+
+/// Strips punctuation from a word for fuzzy comparison.
+/// Keeps alphanumeric chars and apostrophes (for contractions like "don't").
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '\'')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Streaming text split into words that won't change and words that still might
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct StreamingText {
+    pub committed: String,
+    pub tentative: String,
+}
+
+impl StreamingText {
+    /// Committed and tentative text joined
+    pub fn full_text(&self) -> String {
+        match (self.committed.is_empty(), self.tentative.is_empty()) {
+            (_, true) => self.committed.clone(),
+            (true, false) => self.tentative.clone(),
+            (false, false) => format!("{} {}", self.committed, self.tentative),
+        }
+    }
+}
+
+/// Turns overlapping streaming hypotheses into stable text using local agreement:
+/// a word is committed once two consecutive hypotheses agree on it (and on every
+/// word before it). Committed words are never revised or repeated; the rest of the
+/// latest hypothesis is shown as tentative.
+#[derive(Debug, Default)]
+pub struct TextStabilizer {
+    committed: Vec<String>,
+    /// Words of the previous hypothesis after the committed text
+    previous_tail: Vec<String>,
+}
+
+impl TextStabilizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.committed.clear();
+        self.previous_tail.clear();
+    }
+
+    /// Committed text, used as context for the next window's prompt
+    pub fn committed_text(&self) -> String {
+        self.committed.join(" ")
+    }
+
+    /// Adds the transcription of the latest audio window. Windows may start anywhere
+    /// inside the committed text; the overlap is found and skipped.
+    pub fn push(&mut self, hypothesis: &str) -> StreamingText {
+        let words: Vec<&str> = hypothesis.split_whitespace().collect();
+        let tail = &words[self.committed_end(&words)..];
+
+        let agreed = tail
+            .iter()
+            .zip(&self.previous_tail)
+            .take_while(|(new, old)| normalize_word(new) == normalize_word(old))
+            .count();
+        // Commit the newer spelling, which had more context
+        self.committed
+            .extend(tail[..agreed].iter().map(|w| w.to_string()));
+        self.previous_tail = tail[agreed..].iter().map(|w| w.to_string()).collect();
+
+        self.text()
+    }
+
+    /// Current committed and tentative text, without adding a hypothesis
+    pub fn text(&self) -> StreamingText {
+        StreamingText {
+            committed: self.committed.join(" "),
+            tentative: self.previous_tail.join(" "),
+        }
+    }
+
+    /// Index in `words` right after the committed text. The start of the hypothesis is
+    /// aligned against the end of the committed text, scoring +1 per matching word and
+    /// -1 per changed, missing or extra word, so a cut-off first word or a word the model
+    /// dropped doesn't break the overlap. The best-scoring end wins, the later one on a
+    /// tie; without a positive score the whole hypothesis is new.
+    fn committed_end(&self, words: &[&str]) -> usize {
+        // Gaps cost a point each, so a longer committed suffix can't align
+        let start = self.committed.len().saturating_sub(words.len() * 2);
+        let committed: Vec<String> = self.committed[start..]
+            .iter()
+            .map(|w| normalize_word(w))
+            .collect();
+        let words: Vec<String> = words.iter().map(|w| normalize_word(w)).collect();
+
+        // scores[j]: best alignment of words[..j] ending at the current committed word,
+        // starting anywhere in the committed text
+        let mut scores: Vec<i32> = (0..=words.len() as i32).map(|j| -j).collect();
+        for committed_word in &committed {
+            let mut next = vec![0; words.len() + 1];
+            for (j, word) in words.iter().enumerate() {
+                let substitution = if word == committed_word { 1 } else { -1 };
+                next[j + 1] = (scores[j] + substitution)
+                    .max(scores[j + 1] - 1)
+                    .max(next[j] - 1);
+            }
+            scores = next;
+        }
+
+        let (end, score) = scores
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, score)| **score)
+            .unwrap_or((0, &0));
+        if *score > 0 {
+            end
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_word, StreamingText, TextStabilizer};
+
+    /// Feeds `hypotheses` in order and returns (committed, tentative) after each
+    fn replay(hypotheses: &[&str]) -> Vec<(String, String)> {
+        let mut stabilizer = TextStabilizer::new();
+        hypotheses
+            .iter()
+            .map(|h| {
+                let text = stabilizer.push(h);
+                (text.committed, text.tentative)
+            })
+            .collect()
+    }
+
+    fn pair(committed: &str, tentative: &str) -> (String, String) {
+        (committed.to_string(), tentative.to_string())
+    }
+
+    #[test]
+    fn test_normalize_word() {
+        assert_eq!(normalize_word("all,"), "all");
+        assert_eq!(normalize_word("glitchy."), "glitchy");
+        assert_eq!(normalize_word("don't"), "don't");
+        assert_eq!(normalize_word("Hello!"), "hello");
+        assert_eq!(normalize_word("(test)"), "test");
+    }
+
+    #[test]
+    fn test_growing_hypotheses_commit_after_agreement() {
+        let steps = replay(&[
+            "Hello",
+            "Hello world",
+            "Hello world, this is",
+            "Hello world, this is a test.",
+        ]);
+        assert_eq!(
+            steps,
+            vec![
+                pair("", "Hello"),
+                pair("Hello", "world"),
+                pair("Hello world,", "this is"),
+                pair("Hello world, this is", "a test."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flickering_word_stays_tentative() {
+        let steps = replay(&[
+            "the quick",
+            "the quack brown",
+            "the quick brown fox",
+            "the quick brown fox jumps",
+        ]);
+        assert_eq!(
+            steps,
+            vec![
+                pair("", "the quick"),
+                pair("the", "quack brown"),
+                pair("the", "quick brown fox"),
+                pair("the quick brown fox", "jumps"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_committed_words_are_not_revised() {
+        let steps = replay(&["I saw the", "I saw the cat", "I sew a cat sat"]);
+        assert_eq!(steps[1], pair("I saw the", "cat"));
+        // The model changed its mind about committed words; they stay as they were
+        assert_eq!(steps[2].0, "I saw the");
+    }
+
+    #[test]
+    fn test_sliding_windows_do_not_duplicate() {
+        // Windows stop covering the start of the recording and may cut the first word
+        let steps = replay(&[
+            "First of all, the voice seems",
+            "First of all the voice seems to be",
+            "rst of all the voice seems to be slightly glitchy",
+            "the voice seems to be slightly glitchy and I still",
+            "seems to be slightly glitchy and I still notice a delay.",
+        ]);
+        assert_eq!(
+            steps,
+            vec![
+                pair("", "First of all, the voice seems"),
+                pair("First of all the voice seems", "to be"),
+                pair("First of all the voice seems to be", "slightly glitchy"),
+                pair(
+                    "First of all the voice seems to be slightly glitchy",
+                    "and I still"
+                ),
+                pair(
+                    "First of all the voice seems to be slightly glitchy and I still",
+                    "notice a delay."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_overlap_is_not_repeated() {
+        let steps = replay(&["I saw the", "I saw the", "the cat", "the cat"]);
+        assert_eq!(steps[1], pair("I saw the", ""));
+        // A window starting on the last committed word doesn't repeat it
+        assert_eq!(steps[2], pair("I saw the", "cat"));
+        assert_eq!(steps[3], pair("I saw the cat", ""));
+    }
+
+    #[test]
+    fn test_inexact_overlap_is_not_repeated() {
+        let steps = replay(&[
+            "we should ship the release on friday",
+            "we should ship the release on friday",
+            // The model dropped "the" and cut "should" short this time
+            "ould ship release on friday morning",
+            "ould ship release on friday morning",
+        ]);
+        assert_eq!(steps[1].0, "we should ship the release on friday");
+        assert_eq!(
+            steps[2],
+            pair("we should ship the release on friday", "morning")
+        );
+        assert_eq!(
+            steps[3],
+            pair("we should ship the release on friday morning", "")
+        );
+    }
+
+    #[test]
+    fn test_window_without_overlap_is_new_speech() {
+        let steps = replay(&["good morning", "good morning", "how are you", "how are you"]);
+        assert_eq!(steps[3], pair("good morning how are you", ""));
+    }
+
+    #[test]
+    fn test_full_text_joins_parts() {
+        let text = StreamingText {
+            committed: "hello".to_string(),
+            tentative: "world".to_string(),
+        };
+        assert_eq!(text.full_text(), "hello world");
+        assert_eq!(StreamingText::default().full_text(), "");
+
+        let mut stabilizer = TextStabilizer::new();
+        stabilizer.push("hello world");
+        stabilizer.push("hello world again");
+        assert_eq!(stabilizer.committed_text(), "hello world");
+        stabilizer.reset();
+        assert_eq!(stabilizer.text(), StreamingText::default());
+    }
+}
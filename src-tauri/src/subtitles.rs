@@ -0,0 +1,299 @@
+use anyhow::Result;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::audio_toolkit::decode_audio_file;
+use crate::managers::history::HistoryManager;
+use crate::managers::inference_queue::JobPriority;
+use crate::managers::transcription::{TranscriptSegment, TranscriptionManager};
+
+/// Longest subtitle line, per common broadcast guidelines
+const MAX_LINE_CHARS: usize = 42;
+const MAX_LINES: usize = 2;
+/// Cues are split before they stay on screen longer than this (seconds)
+const MAX_CUE_SECONDS: f32 = 7.0;
+/// Short cues are stretched to this (seconds), unless the next cue starts earlier
+const MIN_CUE_SECONDS: f32 = 1.0;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+/// One subtitle on screen: up to `MAX_LINES` lines of text
+#[derive(Clone, Debug, PartialEq)]
+struct Cue {
+    start: f32,
+    end: f32,
+    lines: Vec<String>,
+}
+
+/// Accumulates words into a cue until a line, duration or segment limit is hit
+#[derive(Default)]
+struct CueBuilder {
+    cues: Vec<Cue>,
+    start: f32,
+    end: f32,
+    lines: Vec<String>,
+    line: String,
+}
+
+impl CueBuilder {
+    fn push_word(&mut self, text: &str, start: f32, end: f32) {
+        let is_empty = self.lines.is_empty() && self.line.is_empty();
+        if !is_empty && end - self.start > MAX_CUE_SECONDS {
+            self.flush();
+        }
+
+        let line_len = self.line.chars().count() + 1 + text.chars().count();
+        if !self.line.is_empty() && line_len > MAX_LINE_CHARS {
+            if self.lines.len() + 1 >= MAX_LINES {
+                self.flush();
+            } else {
+                self.lines.push(std::mem::take(&mut self.line));
+            }
+        }
+
+        if self.lines.is_empty() && self.line.is_empty() {
+            self.start = start;
+        }
+        if !self.line.is_empty() {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+        self.end = end;
+    }
+
+    fn flush(&mut self) {
+        if !self.line.is_empty() {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+        if self.lines.is_empty() {
+            return;
+        }
+        self.cues.push(Cue {
+            start: self.start,
+            end: self.end,
+            lines: std::mem::take(&mut self.lines),
+        });
+    }
+
+    fn finish(mut self) -> Vec<Cue> {
+        self.flush();
+        let mut cues = self.cues;
+
+        // Give short cues time to be read without overlapping the next one
+        let next_starts: Vec<Option<f32>> = cues
+            .iter()
+            .skip(1)
+            .map(|c| Some(c.start))
+            .chain([None])
+            .collect();
+        for (cue, next_start) in cues.iter_mut().zip(next_starts) {
+            if cue.end - cue.start < MIN_CUE_SECONDS {
+                let stretched = cue.start + MIN_CUE_SECONDS;
+                cue.end = next_start.map_or(stretched, |next| stretched.min(next).max(cue.end));
+            }
+        }
+        cues
+    }
+}
+
+/// Breaks timed segments into subtitle cues. Cues never span segments.
+fn build_cues(segments: &[TranscriptSegment]) -> Vec<Cue> {
+    let mut builder = CueBuilder::default();
+    for segment in segments {
+        if segment.words.is_empty() {
+            builder.push_word(segment.text.trim(), segment.start, segment.end);
+        } else {
+            for word in &segment.words {
+                builder.push_word(&word.text, word.start, word.end);
+            }
+        }
+        builder.flush();
+    }
+    builder.finish()
+}
+
+fn format_timestamp(seconds: f32, decimal_separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        decimal_separator,
+        millis % 1000
+    )
+}
+
+/// Renders segments as an SRT or WebVTT document
+pub fn render_subtitles(segments: &[TranscriptSegment], format: SubtitleFormat) -> String {
+    let separator = match format {
+        SubtitleFormat::Srt => ',',
+        SubtitleFormat::Vtt => '.',
+    };
+
+    let mut output = String::new();
+    if format == SubtitleFormat::Vtt {
+        output.push_str("WEBVTT\n\n");
+    }
+
+    for (index, cue) in build_cues(segments).iter().enumerate() {
+        if format == SubtitleFormat::Srt {
+            output.push_str(&format!("{}\n", index + 1));
+        }
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start, separator),
+            format_timestamp(cue.end, separator),
+            cue.lines.join("\n")
+        ));
+    }
+    output
+}
+
+/// Writes subtitles for a history entry to the Downloads folder and returns the path.
+/// Entries saved without timings are re-transcribed from their recording first.
+pub async fn export_subtitles(
+    app: &AppHandle,
+    history_id: i64,
+    format: SubtitleFormat,
+) -> Result<PathBuf> {
+    let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+    let entry = hm
+        .get_entry_by_id(history_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("History entry {} not found", history_id))?;
+
+    let mut segments = hm.get_transcription_segments(history_id).await?;
+    if segments.is_empty() {
+        debug!(
+            "No stored timings for entry {}, re-transcribing",
+            history_id
+        );
+        let audio_path = hm.get_audio_file_path(&entry.file_name);
+        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+        segments = tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+            let samples = decode_audio_file(&audio_path)?;
+            tm.initiate_model_load();
+            Ok(tm.transcribe_timed(samples, JobPriority::Import)?.segments)
+        })
+        .await??;
+        hm.save_transcription_segments(history_id, &segments)?;
+    }
+
+    if segments.is_empty() {
+        anyhow::bail!("No speech found in this recording");
+    }
+
+    let export_dir = app
+        .path()
+        .download_dir()
+        .or_else(|_| app.path().app_data_dir())?;
+    let stem = entry
+        .file_name
+        .rsplit_once('.')
+        .map_or(entry.file_name.as_str(), |(stem, _)| stem);
+    let path = export_dir.join(format!("{}.{}", stem, format.extension()));
+    std::fs::write(&path, render_subtitles(&segments, format))?;
+
+    info!("Exported subtitles for entry {} to {:?}", history_id, path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::transcription::WordTiming;
+
+    fn segment(words: &[(&str, f32, f32)]) -> TranscriptSegment {
+        let words: Vec<WordTiming> = words
+            .iter()
+            .map(|&(text, start, end)| WordTiming {
+                text: text.to_string(),
+                start,
+                end,
+                confidence: None,
+            })
+            .collect();
+        TranscriptSegment {
+            text: words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            start: words.first().unwrap().start,
+            end: words.last().unwrap().end,
+            words,
+        }
+    }
+
+    #[test]
+    fn test_render_srt_and_vtt() {
+        let segments = vec![
+            segment(&[("Hello", 0.0, 0.4), ("there.", 0.5, 1.2)]),
+            segment(&[("Bye.", 3661.0, 3661.5)]),
+        ];
+
+        assert_eq!(
+            render_subtitles(&segments, SubtitleFormat::Srt),
+            "1\n00:00:00,000 --> 00:00:01,200\nHello there.\n\n\
+             2\n01:01:01,000 --> 01:01:02,000\nBye.\n\n"
+        );
+        assert_eq!(
+            render_subtitles(&segments, SubtitleFormat::Vtt),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.200\nHello there.\n\n\
+             01:01:01.000 --> 01:01:02.000\nBye.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_long_segments_wrap_and_split() {
+        // 30 words of 9 chars ("wordxxxxN") over 30 seconds
+        let words: Vec<(String, f32, f32)> = (0..30)
+            .map(|i| (format!("wordxxxx{}", i % 10), i as f32, i as f32 + 0.9))
+            .collect();
+        let words: Vec<(&str, f32, f32)> =
+            words.iter().map(|(t, s, e)| (t.as_str(), *s, *e)).collect();
+        let cues = build_cues(&[segment(&words)]);
+
+        assert!(cues.len() > 1);
+        for cue in &cues {
+            assert!(cue.lines.len() <= MAX_LINES);
+            assert!(cue
+                .lines
+                .iter()
+                .all(|l| l.chars().count() <= MAX_LINE_CHARS));
+            assert!(cue.end - cue.start <= MAX_CUE_SECONDS);
+        }
+        assert!(cues.windows(2).all(|pair| pair[0].end <= pair[1].start));
+    }
+
+    #[test]
+    fn test_short_cue_is_stretched_up_to_next_cue() {
+        let cues = build_cues(&[
+            segment(&[("Hi.", 0.0, 0.2)]),
+            segment(&[("Yes.", 0.6, 0.9)]),
+            segment(&[("Ok.", 5.0, 5.1)]),
+        ]);
+        let ends: Vec<f32> = cues.iter().map(|c| c.end).collect();
+        for (end, expected) in ends.iter().zip([0.6, 1.6, 6.0]) {
+            assert!((end - expected).abs() < 1e-4, "{:?}", ends);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::actions::ACTION_MAP;
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::import::ImportManager;
 use crate::ManagedToggleState;
 use log::{debug, warn};
 use std::sync::Arc;
@@ -50,6 +51,12 @@ pub fn cancel_current_operation(app: &AppHandle) {
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
 
+    // Cancel queued and running batch transcriptions
+    let import_manager = app.state::<Arc<ImportManager>>();
+    if let Err(e) = import_manager.cancel_all_jobs() {
+        warn!("Failed to cancel transcription jobs: {}", e);
+    }
+
     // Update tray icon and menu to idle state
     change_tray_icon(app, crate::tray::TrayIconState::Idle);
 
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
 import { useEffect, useState } from "react";
 import { toast, Toaster } from "sonner";
 import "./index.css";
@@ -9,6 +10,7 @@ import Onboarding from "./components/onboarding";
 import { Sidebar, SidebarSection, SECTIONS_CONFIG } from "./components/Sidebar";
 import TitleBar from "./components/TitleBar";
 import { useSettings } from "./hooks/useSettings";
+import { useSettingsStore } from "./stores/settingsStore";
 import { useTheme } from "./hooks/useTheme";
 
 const renderSettingsContent = (
@@ -65,6 +67,51 @@ function App() {
     };
   }, []);
 
+  // The backend changes settings on its own in a few cases (e.g. the monthly
+  // ghostwriter budget switching output back to transcripts)
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    listen("settings-changed", () => {
+      useSettingsStore.getState().refreshSettings();
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // Dropping audio files or folders onto the window queues them for
+  // background transcription into history
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getCurrentWebview()
+      .onDragDropEvent(async (event) => {
+        if (event.payload.type !== "drop") return;
+
+        try {
+          const jobs = await invoke<unknown[]>("enqueue_transcription_jobs", {
+            paths: event.payload.paths,
+          });
+          toast(`Transcribing ${jobs.length} file${jobs.length === 1 ? "" : "s"} in the background`, {
+            description: "Progress is shown in History",
+          });
+        } catch (error) {
+          toast.error(String(error), { description: "Import Failed" });
+        }
+      })
+      .then((fn) => {
+        unlisten = fn;
+      });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
   // Handle keyboard shortcuts for debug mode toggle and command palette
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
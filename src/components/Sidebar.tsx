@@ -42,7 +42,8 @@ export const SECTIONS_CONFIG = {
     label: "Profiles",
     icon: Users,
     component: ProfileManager,
-    enabled: (settings) => settings?.output_mode === "ghostwriter",
+    enabled: (settings) =>
+      settings?.output_mode === "ghostwriter" || settings?.output_mode === "reply",
   },
   history: {
     label: "History",
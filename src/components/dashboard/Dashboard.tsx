@@ -28,6 +28,10 @@ interface HistoryStats {
   total_duration_seconds: number;
   total_words: number;
   saved_count: number;
+  total_prompt_tokens: number;
+  total_completion_tokens: number;
+  total_ghostwriter_cost: number;
+  month_ghostwriter_cost: number;
 }
 
 interface Profile {
@@ -335,8 +339,20 @@ export const Dashboard: React.FC<DashboardProps> = ({ onNavigate }) => {
         </motion.div>
       )}
 
+      {/* Ghostwriter spend */}
+      {stats && stats.total_prompt_tokens + stats.total_completion_tokens > 0 && (
+        <motion.div variants={itemVariants} className="text-sm text-muted-foreground">
+          Ghostwriter usage:{" "}
+          {formatNumber(stats.total_prompt_tokens + stats.total_completion_tokens)} tokens
+          {" · "}${stats.month_ghostwriter_cost.toFixed(2)} this month
+          {getSetting("ghostwriter_monthly_budget") != null &&
+            ` of $${getSetting("ghostwriter_monthly_budget")!.toFixed(2)} budget`}
+          {" · "}${stats.total_ghostwriter_cost.toFixed(2)} total
+        </motion.div>
+      )}
+
       {/* Profile Selector - Only show in ghostwriter mode */}
-      {getSetting("output_mode") === "ghostwriter" && (
+      {(getSetting("output_mode") === "ghostwriter" || getSetting("output_mode") === "reply") && (
         <motion.div variants={itemVariants} className="space-y-3">
           <div className="flex items-center justify-between">
             <h2 className="text-lg font-sans font-bold text-foreground">
@@ -0,0 +1,147 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Plus, Trash2, Check } from "lucide-react";
+import React, { useEffect, useState } from "react";
+import { Button } from "../ui/Button";
+
+interface ProfileExample {
+  id: number;
+  profile_id: number;
+  input: string;
+  output: string;
+  created_at: number;
+}
+
+interface ProfileExamplesProps {
+  profileId: number;
+}
+
+const textareaClass =
+  "w-full px-3 py-2 text-sm rounded-lg border border-border bg-card text-foreground focus:outline-none focus:ring-2 focus:ring-ring";
+
+// Few-shot examples (dictation -> desired output) that teach the ghostwriter a profile's style
+export const ProfileExamples: React.FC<ProfileExamplesProps> = ({ profileId }) => {
+  const [examples, setExamples] = useState<ProfileExample[]>([]);
+  const [newInput, setNewInput] = useState("");
+  const [newOutput, setNewOutput] = useState("");
+
+  useEffect(() => {
+    loadExamples();
+  }, [profileId]);
+
+  const loadExamples = async () => {
+    try {
+      const data: ProfileExample[] = await invoke("get_profile_examples", { profileId });
+      setExamples(data);
+    } catch (error) {
+      console.error("Failed to load profile examples:", error);
+    }
+  };
+
+  const handleAdd = async () => {
+    try {
+      await invoke("add_profile_example", { profileId, input: newInput, output: newOutput });
+      setNewInput("");
+      setNewOutput("");
+      loadExamples();
+    } catch (error) {
+      console.error("Failed to add example:", error);
+      alert(String(error));
+    }
+  };
+
+  const handleSave = async (example: ProfileExample) => {
+    try {
+      await invoke("update_profile_example", {
+        id: example.id,
+        input: example.input,
+        output: example.output,
+      });
+      loadExamples();
+    } catch (error) {
+      console.error("Failed to update example:", error);
+      alert(String(error));
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await invoke("delete_profile_example", { id });
+      loadExamples();
+    } catch (error) {
+      console.error("Failed to delete example:", error);
+    }
+  };
+
+  const updateLocal = (id: number, field: "input" | "output", value: string) => {
+    setExamples(examples.map((e) => (e.id === id ? { ...e, [field]: value } : e)));
+  };
+
+  return (
+    <div className="space-y-3">
+      <label className="block text-sm font-medium text-foreground">
+        Examples
+      </label>
+      <p className="text-xs text-muted-foreground">
+        Show the AI what you say and what you want back. Examples are sent with every
+        dictation in this profile.
+      </p>
+
+      {examples.map((example) => (
+        <div key={example.id} className="grid grid-cols-[1fr_1fr_auto] gap-2 items-start">
+          <textarea
+            value={example.input}
+            onChange={(e) => updateLocal(example.id, "input", e.target.value)}
+            className={textareaClass}
+            rows={3}
+          />
+          <textarea
+            value={example.output}
+            onChange={(e) => updateLocal(example.id, "output", e.target.value)}
+            className={textareaClass}
+            rows={3}
+          />
+          <div className="flex flex-col gap-1">
+            <button
+              onClick={() => handleSave(example)}
+              className="p-2 rounded-lg hover:bg-muted transition-colors"
+              title="Save example"
+            >
+              <Check className="w-4 h-4" />
+            </button>
+            <button
+              onClick={() => handleDelete(example.id)}
+              className="p-2 rounded-lg hover:bg-muted transition-colors text-destructive"
+              title="Delete example"
+            >
+              <Trash2 className="w-4 h-4" />
+            </button>
+          </div>
+        </div>
+      ))}
+
+      <div className="grid grid-cols-[1fr_1fr_auto] gap-2 items-start">
+        <textarea
+          value={newInput}
+          onChange={(e) => setNewInput(e.target.value)}
+          placeholder="What you'd dictate..."
+          className={textareaClass}
+          rows={3}
+        />
+        <textarea
+          value={newOutput}
+          onChange={(e) => setNewOutput(e.target.value)}
+          placeholder="What you want pasted..."
+          className={textareaClass}
+          rows={3}
+        />
+        <Button
+          onClick={handleAdd}
+          disabled={!newInput.trim() || !newOutput.trim()}
+          title="Add example"
+        >
+          <Plus className="w-4 h-4" />
+        </Button>
+      </div>
+    </div>
+  );
+};
@@ -5,6 +5,9 @@ import React, { useEffect, useState } from "react";
 import { Button } from "../ui/Button";
 import { Input } from "../ui/Input";
 import { useSettings } from "../../hooks/useSettings";
+import { ProfileExamples } from "./ProfileExamples";
+import { DecodingParamsFields } from "../settings/DecodingParamsFields";
+import { DecodingParams } from "../../lib/types";
 
 interface Profile {
   id: number;
@@ -13,11 +16,32 @@ interface Profile {
   color: string;
   icon: string;
   custom_instructions: string | null;
+  ghostwriter_provider: string | null;
+  ghostwriter_model: string | null;
+  temperature: number | null;
+  top_p: number | null;
+  max_tokens: number | null;
+  decoding_params: DecodingParams;
+  vocabulary: string[];
   created_at: number;
   updated_at: number;
 }
 
 const PROFILE_ICONS = ["∅", "📊", "📝", "💻", "✉️", "📞", "🎯", "💡", "📚", "🎨", "🔬"];
+// Empty inputs mean "no override"
+const parseOptionalNumber = (value: string): number | null => {
+  const trimmed = value.trim();
+  if (!trimmed) return null;
+  const parsed = Number(trimmed);
+  return Number.isFinite(parsed) ? parsed : null;
+};
+// One term per line or comma
+const parseVocabulary = (value: string): string[] =>
+  value
+    .split(/[,\n]/)
+    .map((term) => term.trim())
+    .filter(Boolean);
+
 const PROFILE_COLORS = [
   "#2d5da1", // Blue pen
   "#10B981", // Green
@@ -41,6 +65,13 @@ export const ProfileManager: React.FC = () => {
     color: PROFILE_COLORS[0],
     icon: PROFILE_ICONS[0],
     custom_instructions: "",
+    ghostwriter_provider: "",
+    ghostwriter_model: "",
+    temperature: "",
+    top_p: "",
+    max_tokens: "",
+    decoding_params: {} as DecodingParams,
+    vocabulary: "",
   });
 
   useEffect(() => {
@@ -103,12 +134,20 @@ export const ProfileManager: React.FC = () => {
         color: formData.color,
         icon: formData.icon,
         customInstructions: formData.custom_instructions,
+        ghostwriterProvider: formData.ghostwriter_provider || null,
+        ghostwriterModel: formData.ghostwriter_model.trim() || null,
+        temperature: parseOptionalNumber(formData.temperature),
+        topP: parseOptionalNumber(formData.top_p),
+        maxTokens: parseOptionalNumber(formData.max_tokens),
+        decodingParams: formData.decoding_params,
+        vocabulary: parseVocabulary(formData.vocabulary),
       });
       resetForm();
       setIsCreating(false);
       loadProfiles();
     } catch (error) {
       console.error("Failed to create profile:", error);
+      alert(String(error));
     }
   };
 
@@ -126,12 +165,20 @@ export const ProfileManager: React.FC = () => {
         color: formData.color,
         icon: formData.icon,
         customInstructions: formData.custom_instructions,
+        ghostwriterProvider: formData.ghostwriter_provider || null,
+        ghostwriterModel: formData.ghostwriter_model.trim() || null,
+        temperature: parseOptionalNumber(formData.temperature),
+        topP: parseOptionalNumber(formData.top_p),
+        maxTokens: parseOptionalNumber(formData.max_tokens),
+        decodingParams: formData.decoding_params,
+        vocabulary: parseVocabulary(formData.vocabulary),
       });
       resetForm();
       setEditingId(null);
       loadProfiles();
     } catch (error) {
       console.error("Failed to update profile:", error);
+      alert(String(error));
     }
   };
 
@@ -153,6 +200,13 @@ export const ProfileManager: React.FC = () => {
       color: profile.color,
       icon: profile.icon,
       custom_instructions: profile.custom_instructions || "",
+      ghostwriter_provider: profile.ghostwriter_provider || "",
+      ghostwriter_model: profile.ghostwriter_model || "",
+      temperature: profile.temperature?.toString() ?? "",
+      top_p: profile.top_p?.toString() ?? "",
+      max_tokens: profile.max_tokens?.toString() ?? "",
+      decoding_params: profile.decoding_params ?? {},
+      vocabulary: (profile.vocabulary ?? []).join("\n"),
     });
   };
 
@@ -163,6 +217,13 @@ export const ProfileManager: React.FC = () => {
       color: PROFILE_COLORS[0],
       icon: PROFILE_ICONS[0],
       custom_instructions: "",
+      ghostwriter_provider: "",
+      ghostwriter_model: "",
+      temperature: "",
+      top_p: "",
+      max_tokens: "",
+      decoding_params: {},
+      vocabulary: "",
     });
   };
 
@@ -305,6 +366,9 @@ export const ProfileManager: React.FC = () => {
                   rows={4}
                   maxLength={10000}
                 />
+                <div className="mt-1 text-xs text-muted-foreground">
+                  Variables: {"{date} {time} {weekday} {language} {profile_name} {clipboard} {user_name}"}
+                </div>
                 <div className="mt-1 text-xs text-muted-foreground">
                   {formData.custom_instructions.trim().length} / 10,000 characters
                   {formData.custom_instructions.trim().length < 20 && formData.custom_instructions.trim().length > 0 &&
@@ -316,6 +380,127 @@ export const ProfileManager: React.FC = () => {
                 </div>
               </div>
 
+              {/* Ghostwriter Provider */}
+              <div>
+                <label className="block text-sm font-medium text-foreground mb-2">
+                  Ghostwriter Provider
+                </label>
+                <select
+                  value={formData.ghostwriter_provider}
+                  onChange={(e) =>
+                    setFormData({
+                      ...formData,
+                      ghostwriter_provider: e.target.value,
+                    })
+                  }
+                  className="w-full px-3 py-2 rounded-lg border border-border bg-card text-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+                >
+                  <option value="">Use global setting</option>
+                  <option value="openrouter">OpenRouter</option>
+                  <option value="openai_compatible">OpenAI-compatible server</option>
+                </select>
+              </div>
+
+              {/* Model and generation parameters */}
+              <div>
+                <label className="block text-sm font-medium text-foreground mb-2">
+                  Model
+                </label>
+                <Input
+                  value={formData.ghostwriter_model}
+                  onChange={(e) =>
+                    setFormData({ ...formData, ghostwriter_model: e.target.value })
+                  }
+                  placeholder="Use the provider's model (e.g. openai/gpt-4o)"
+                />
+              </div>
+              <div className="grid grid-cols-3 gap-3">
+                <div>
+                  <label className="block text-sm font-medium text-foreground mb-2">
+                    Temperature
+                  </label>
+                  <Input
+                    type="number"
+                    min={0}
+                    max={2}
+                    step={0.1}
+                    value={formData.temperature}
+                    onChange={(e) =>
+                      setFormData({ ...formData, temperature: e.target.value })
+                    }
+                    placeholder="Default"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium text-foreground mb-2">
+                    Top P
+                  </label>
+                  <Input
+                    type="number"
+                    min={0}
+                    max={1}
+                    step={0.05}
+                    value={formData.top_p}
+                    onChange={(e) =>
+                      setFormData({ ...formData, top_p: e.target.value })
+                    }
+                    placeholder="Default"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium text-foreground mb-2">
+                    Max Tokens
+                  </label>
+                  <Input
+                    type="number"
+                    min={1}
+                    step={100}
+                    value={formData.max_tokens}
+                    onChange={(e) =>
+                      setFormData({ ...formData, max_tokens: e.target.value })
+                    }
+                    placeholder="Auto"
+                  />
+                </div>
+              </div>
+
+              {/* Whisper decoding overrides */}
+              <div>
+                <label className="block text-sm font-medium text-foreground mb-2">
+                  Transcription
+                </label>
+                <DecodingParamsFields
+                  value={formData.decoding_params}
+                  onChange={(decoding_params) =>
+                    setFormData({ ...formData, decoding_params })
+                  }
+                  showLanguage
+                  placeholder="Global"
+                />
+              </div>
+
+              {/* Terms the speech model is prompted with */}
+              <div>
+                <label className="block text-sm font-medium text-foreground mb-2">
+                  Vocabulary
+                </label>
+                <textarea
+                  value={formData.vocabulary}
+                  onChange={(e) =>
+                    setFormData({ ...formData, vocabulary: e.target.value })
+                  }
+                  placeholder="Names and terms to recognise, one per line"
+                  className="w-full px-3 py-2 rounded-lg border border-border bg-card text-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+                  rows={3}
+                />
+                <div className="mt-1 text-xs text-muted-foreground">
+                  Added to your custom words when transcribing with Whisper.
+                </div>
+              </div>
+
+              {/* Few-shot examples (only once the profile exists) */}
+              {editingId && <ProfileExamples profileId={editingId} />}
+
               {/* Actions */}
               <div className="flex gap-2 pt-2">
                 <Button
@@ -0,0 +1,26 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface AccurateFinalPassProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const AccurateFinalPass: React.FC<AccurateFinalPassProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("accurate_final_pass") ?? false}
+        onChange={(enabled) => updateSetting("accurate_final_pass", enabled)}
+        isUpdating={isUpdating("accurate_final_pass")}
+        label="Accurate Final Pass"
+        description="After you stop speaking, transcribe the whole recording again in chunks split at pauses instead of pasting the live preview. Slower, but more accurate for long recordings."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
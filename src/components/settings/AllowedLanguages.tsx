@@ -0,0 +1,68 @@
+import React from "react";
+import { X } from "lucide-react";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Dropdown } from "../ui/Dropdown";
+import { useSettings } from "../../hooks/useSettings";
+import { LANGUAGES } from "../../lib/constants/languages";
+
+interface AllowedLanguagesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+// Limits auto-detection to the languages the user actually speaks
+export const AllowedLanguages: React.FC<AllowedLanguagesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const allowed = getSetting("allowed_languages") ?? [];
+
+    const options = LANGUAGES.filter(
+      (language) => language.value !== "auto" && !allowed.includes(language.value),
+    );
+
+    const labelFor = (code: string) =>
+      LANGUAGES.find((language) => language.value === code)?.label ?? code;
+
+    return (
+      <SettingContainer
+        title="Auto-Detect Languages"
+        description="Auto-detection only picks from these languages. Leave empty to allow every language."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-wrap items-center gap-2">
+          {allowed.map((code) => (
+            <span
+              key={code}
+              className="flex items-center gap-1 px-2 py-1 text-xs rounded-lg bg-secondary text-foreground"
+            >
+              {labelFor(code)}
+              <button
+                onClick={() =>
+                  updateSetting(
+                    "allowed_languages",
+                    allowed.filter((c) => c !== code),
+                  )
+                }
+                disabled={isUpdating("allowed_languages")}
+                className="text-muted-foreground hover:text-foreground"
+                title={`Remove ${labelFor(code)}`}
+              >
+                <X className="w-3 h-3" />
+              </button>
+            </span>
+          ))}
+          <Dropdown
+            options={options}
+            selectedValue={null}
+            onSelect={(code) => updateSetting("allowed_languages", [...allowed, code])}
+            placeholder={allowed.length === 0 ? "All languages" : "Add language..."}
+            disabled={isUpdating("allowed_languages")}
+            className="min-w-[160px]"
+          />
+        </div>
+      </SettingContainer>
+    );
+  },
+);
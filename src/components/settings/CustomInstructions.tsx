@@ -1,4 +1,5 @@
 import React, { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { SettingContainer } from "../ui/SettingContainer";
 import { useSettingsStore } from "../../stores/settingsStore";
 
@@ -13,6 +14,7 @@ const DEFAULT_INSTRUCTIONS = "Improve grammar, spelling, clarity, and flow while
 export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
   ({ descriptionMode = "tooltip", grouped = false }) => {
     const [localValue, setLocalValue] = useState(DEFAULT_INSTRUCTIONS);
+    const [error, setError] = useState<string | null>(null);
     const timeoutRef = useRef<NodeJS.Timeout | null>(null);
 
     // Load initial value only once on mount
@@ -31,6 +33,16 @@ export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
       };
     }, []);
 
+    // Saved directly so template errors (unknown or unclosed variables) can be shown
+    const save = (value: string) => {
+      invoke("change_custom_instructions_setting", { instructions: value })
+        .then(() => {
+          setError(null);
+          return useSettingsStore.getState().refreshSettings();
+        })
+        .catch((e) => setError(String(e)));
+    };
+
     const handleChange = (e: React.ChangeEvent<HTMLTextAreaElement>) => {
       const value = e.target.value;
       if (value.length <= MAX_LENGTH) {
@@ -40,9 +52,7 @@ export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
         if (timeoutRef.current) {
           clearTimeout(timeoutRef.current);
         }
-        timeoutRef.current = setTimeout(() => {
-          useSettingsStore.getState().updateSetting("custom_instructions", value);
-        }, 500);
+        timeoutRef.current = setTimeout(() => save(value), 500);
       }
     };
 
@@ -51,7 +61,7 @@ export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
       if (timeoutRef.current) {
         clearTimeout(timeoutRef.current);
       }
-      useSettingsStore.getState().updateSetting("custom_instructions", localValue);
+      save(localValue);
     };
 
     const charCount = localValue.length;
@@ -60,7 +70,7 @@ export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
     return (
       <SettingContainer
         title="Custom Instructions"
-        description="Tell the AI how to rewrite your transcriptions. Be specific about the tone, style, and any transformations you want. This system prompt guides the ghostwriting process."
+        description="Tell the AI how to rewrite your transcriptions. Be specific about the tone, style, and any transformations you want. This system prompt guides the ghostwriting process. Variables such as {date}, {time}, {weekday}, {language}, {profile_name}, {clipboard} and {user_name} are filled in when you dictate."
         descriptionMode={descriptionMode}
         grouped={grouped}
         layout="stacked"
@@ -76,6 +86,7 @@ export const CustomInstructions: React.FC<CustomInstructionsProps> = React.memo(
             rows={6}
             className="w-full px-3 py-2 text-sm bg-muted border border-border rounded resize-y transition-all duration-150 hover:bg-primary/10 hover:border-primary focus:outline-none focus:bg-primary/20 focus:border-primary disabled:opacity-50 disabled:cursor-not-allowed"
           />
+          {error && <div className="text-xs text-red-400">{error}</div>}
           <div className={`text-xs text-right ${isNearLimit ? "text-orange-400" : "text-muted-foreground"}`}>
             {charCount.toLocaleString()} / {MAX_LENGTH.toLocaleString()} characters
           </div>
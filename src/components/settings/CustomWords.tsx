@@ -47,7 +47,7 @@ export const CustomWords: React.FC<CustomWordsProps> = React.memo(
       <>
         <SettingContainer
           title="Custom Words"
-          description="Add words that are often misheard or misspelled during transcription. The system will automatically correct similar-sounding words to match your list."
+          description="Add words that are often misheard or misspelled during transcription. Whisper is prompted with your list, and similar-sounding words are corrected to match it."
           descriptionMode={descriptionMode}
           grouped={grouped}
         >
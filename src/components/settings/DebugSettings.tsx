@@ -1,5 +1,7 @@
 import React from "react";
 import { WordCorrectionThreshold } from "./debug/WordCorrectionThreshold";
+import { TranscriptionQueueMetrics } from "./debug/TranscriptionQueueMetrics";
+import { LowConfidenceThreshold } from "./debug/LowConfidenceThreshold";
 import { SettingsGroup } from "../ui/SettingsGroup";
 import { HistoryLimit } from "./HistoryLimit";
 import { PasteMethodSetting } from "./PasteMethod";
@@ -19,9 +21,11 @@ export const DebugSettings: React.FC = () => {
           description="Choose a sound theme for recording start and stop feedback"
         />
         <WordCorrectionThreshold descriptionMode="tooltip" grouped={true} />
+        <LowConfidenceThreshold descriptionMode="tooltip" grouped={true} />
         <HistoryLimit descriptionMode="tooltip" grouped={true} />
         <AlwaysOnMicrophone descriptionMode="tooltip" grouped={true} />
         <MuteWhileRecording descriptionMode="tooltip" grouped={true} />
+        <TranscriptionQueueMetrics descriptionMode="tooltip" grouped={true} />
       </SettingsGroup>
     </div>
   );
@@ -0,0 +1,129 @@
+import React, { useEffect, useState } from "react";
+import { Input } from "../ui/Input";
+import { DecodingParams } from "../../lib/types";
+import { LANGUAGES } from "../../lib/constants/languages";
+
+type NumericKey = Exclude<keyof DecodingParams, "language" | "translate">;
+
+const NUMERIC_FIELDS: {
+  key: NumericKey;
+  label: string;
+  min: number;
+  max?: number;
+  step: number;
+}[] = [
+  { key: "beam_size", label: "Beam Size", min: 1, max: 16, step: 1 },
+  { key: "best_of", label: "Best Of", min: 1, max: 16, step: 1 },
+  { key: "temperature", label: "Temperature", min: 0, max: 1, step: 0.1 },
+  { key: "temperature_increment", label: "Fallback Increment", min: 0, max: 1, step: 0.1 },
+  { key: "no_speech_threshold", label: "No-Speech Threshold", min: 0, max: 1, step: 0.05 },
+  { key: "threads", label: "Threads", min: 1, step: 1 },
+];
+
+const selectClass =
+  "w-full px-3 py-2 rounded-lg border border-border bg-card text-foreground focus:outline-none focus:ring-2 focus:ring-ring";
+
+const toStrings = (params: DecodingParams) =>
+  Object.fromEntries(
+    NUMERIC_FIELDS.map(({ key }) => [key, params[key]?.toString() ?? ""]),
+  ) as Record<NumericKey, string>;
+
+interface DecodingParamsFieldsProps {
+  value: DecodingParams;
+  onChange: (value: DecodingParams) => void;
+  // Profiles can also override the language and translate settings
+  showLanguage?: boolean;
+  placeholder?: string;
+}
+
+// Whisper decoding options; empty fields fall back to the next level (profile -> global -> engine)
+export const DecodingParamsFields: React.FC<DecodingParamsFieldsProps> = ({
+  value,
+  onChange,
+  showLanguage = false,
+  placeholder = "Default",
+}) => {
+  const [localValues, setLocalValues] = useState(() => toStrings(value));
+
+  useEffect(() => {
+    setLocalValues(toStrings(value));
+  }, [value]);
+
+  const handleBlur = (key: NumericKey) => {
+    const trimmed = localValues[key].trim();
+    const parsed = trimmed ? Number(trimmed) : null;
+    if (parsed !== null && !Number.isFinite(parsed)) {
+      // Revert invalid input to the stored value
+      setLocalValues(toStrings(value));
+      return;
+    }
+    if (parsed !== (value[key] ?? null)) {
+      onChange({ ...value, [key]: parsed });
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="grid grid-cols-3 gap-3">
+        {NUMERIC_FIELDS.map(({ key, label, min, max, step }) => (
+          <div key={key}>
+            <label className="block text-sm font-medium text-foreground mb-2">
+              {label}
+            </label>
+            <Input
+              type="number"
+              min={min}
+              max={max}
+              step={step}
+              value={localValues[key]}
+              onChange={(e) => setLocalValues({ ...localValues, [key]: e.target.value })}
+              onBlur={() => handleBlur(key)}
+              placeholder={placeholder}
+            />
+          </div>
+        ))}
+      </div>
+
+      {showLanguage && (
+        <div className="grid grid-cols-2 gap-3">
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-2">
+              Language
+            </label>
+            <select
+              value={value.language ?? ""}
+              onChange={(e) => onChange({ ...value, language: e.target.value || null })}
+              className={selectClass}
+            >
+              <option value="">Use global setting</option>
+              {LANGUAGES.map((language) => (
+                <option key={language.value} value={language.value}>
+                  {language.label}
+                </option>
+              ))}
+            </select>
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-2">
+              Translate to English
+            </label>
+            <select
+              value={value.translate == null ? "" : String(value.translate)}
+              onChange={(e) =>
+                onChange({
+                  ...value,
+                  translate: e.target.value === "" ? null : e.target.value === "true",
+                })
+              }
+              className={selectClass}
+            >
+              <option value="">Use global setting</option>
+              <option value="true">Yes</option>
+              <option value="false">No</option>
+            </select>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,28 @@
+import React from "react";
+import { SettingsGroup } from "../ui/SettingsGroup";
+import { DecodingParamsFields } from "./DecodingParamsFields";
+import { useSettings } from "../../hooks/useSettings";
+import { DecodingParams } from "../../lib/types";
+
+// Stable fallback so the fields don't reset on every render
+const NO_PARAMS: DecodingParams = {};
+
+export const DecodingParamsSetting: React.FC = () => {
+  const { getSetting, updateSetting } = useSettings();
+  const params = getSetting("decoding_params") ?? NO_PARAMS;
+
+  return (
+    <SettingsGroup
+      title="Whisper Decoding"
+      description="Defaults for Whisper models. Profiles can override each value; empty fields use the engine defaults. The current engine applies the no-speech threshold only."
+    >
+      <div className="p-4">
+        <DecodingParamsFields
+          value={params}
+          onChange={(value) => updateSetting("decoding_params", value)}
+          placeholder="Engine default"
+        />
+      </div>
+    </SettingsGroup>
+  );
+};
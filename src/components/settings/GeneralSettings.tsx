@@ -1,6 +1,7 @@
 import React from "react";
 import { MicrophoneSelector } from "./MicrophoneSelector";
 import { LanguageSelector } from "./LanguageSelector";
+import { AllowedLanguages } from "./AllowedLanguages";
 import { LeadrScribeShortcut } from "./LeadrScribeShortcut";
 import { SettingsGroup } from "../ui/SettingsGroup";
 import { OutputDeviceSelector } from "./OutputDeviceSelector";
@@ -11,20 +12,27 @@ import { VolumeSlider } from "./VolumeSlider";
 import { ShowOverlay } from "./ShowOverlay";
 import { TranslateToEnglish } from "./TranslateToEnglish";
 import { ModelUnloadTimeoutSetting } from "./ModelUnloadTimeout";
+import { StreamingModel } from "./StreamingModel";
+import { AccurateFinalPass } from "./AccurateFinalPass";
+import { LongFormDictation } from "./LongFormDictation";
 import { StartHidden } from "./StartHidden";
 import { AutostartToggle } from "./AutostartToggle";
 import { OutputModeSetting } from "./OutputMode";
 import { OpenRouterApiKey } from "./OpenRouterApiKey";
 import { OpenRouterModel } from "./OpenRouterModel";
 import { CustomInstructions } from "./CustomInstructions";
+import { UserName } from "./UserName";
+import { GhostwriterBudget } from "./GhostwriterBudget";
+import { GhostwriterVerifyHints } from "./GhostwriterVerifyHints";
 import { CustomWords } from "./CustomWords";
+import { DecodingParamsSetting } from "./DecodingParamsSetting";
 import { ThemeSelector } from "./ThemeSelector";
 import ModelSelector from "../model-selector";
 
 export const GeneralSettings: React.FC = () => {
   const { audioFeedbackEnabled, getSetting } = useSettings();
   const outputMode = getSetting("output_mode") || "transcript";
-  const isGhostwriterMode = outputMode === "ghostwriter";
+  const isGhostwriterMode = outputMode === "ghostwriter" || outputMode === "reply";
   const isTranscriptMode = outputMode === "transcript";
 
   return (
@@ -47,12 +55,18 @@ export const GeneralSettings: React.FC = () => {
           <ThemeSelector descriptionMode="tooltip" grouped={true} />
           <LeadrScribeShortcut descriptionMode="tooltip" grouped={true} />
           <LanguageSelector descriptionMode="tooltip" grouped={true} />
+          {getSetting("selected_language") === "auto" && (
+            <AllowedLanguages descriptionMode="tooltip" grouped={true} />
+          )}
           <PushToTalk descriptionMode="tooltip" grouped={true} />
           <StartHidden descriptionMode="tooltip" grouped={true} />
           <AutostartToggle descriptionMode="tooltip" grouped={true} />
           <ShowOverlay descriptionMode="tooltip" grouped={true} />
           <TranslateToEnglish descriptionMode="tooltip" grouped={true} />
           <ModelUnloadTimeoutSetting descriptionMode="tooltip" grouped={true} />
+          <StreamingModel descriptionMode="tooltip" grouped={true} />
+          <AccurateFinalPass descriptionMode="tooltip" grouped={true} />
+          <LongFormDictation descriptionMode="tooltip" grouped={true} />
         </SettingsGroup>
       </div>
 
@@ -77,6 +91,9 @@ export const GeneralSettings: React.FC = () => {
               <OpenRouterApiKey descriptionMode="tooltip" grouped={true} />
               <OpenRouterModel descriptionMode="tooltip" grouped={true} />
               <CustomInstructions descriptionMode="tooltip" grouped={true} />
+              <UserName descriptionMode="tooltip" grouped={true} />
+              <GhostwriterBudget descriptionMode="tooltip" grouped={true} />
+              <GhostwriterVerifyHints descriptionMode="tooltip" grouped={true} />
             </>
           )}
           {isTranscriptMode && (
@@ -84,6 +101,10 @@ export const GeneralSettings: React.FC = () => {
           )}
         </SettingsGroup>
       </div>
+
+      <div className="relative z-10">
+        <DecodingParamsSetting />
+      </div>
     </div>
   );
 };
@@ -0,0 +1,58 @@
+import React, { useState, useEffect } from "react";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettingsStore } from "../../stores/settingsStore";
+
+interface GhostwriterBudgetProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const GhostwriterBudget: React.FC<GhostwriterBudgetProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const [localValue, setLocalValue] = useState("");
+
+    // Load initial value only once on mount
+    useEffect(() => {
+      const budget = useSettingsStore.getState().settings?.ghostwriter_monthly_budget;
+      setLocalValue(budget != null ? budget.toString() : "");
+    }, []);
+
+    const handleBlur = () => {
+      const trimmed = localValue.trim();
+      if (!trimmed) {
+        useSettingsStore.getState().updateSetting("ghostwriter_monthly_budget", null);
+        return;
+      }
+
+      const budget = Number(trimmed);
+      if (Number.isFinite(budget) && budget >= 0) {
+        useSettingsStore.getState().updateSetting("ghostwriter_monthly_budget", budget);
+      } else {
+        // Revert invalid input to the stored value
+        const stored = useSettingsStore.getState().settings?.ghostwriter_monthly_budget;
+        setLocalValue(stored != null ? stored.toString() : "");
+      }
+    };
+
+    return (
+      <SettingContainer
+        title="Monthly Budget (USD)"
+        description="Once ghostwriting has cost this much in the current month, output switches back to plain transcripts. Leave empty for no limit. Only providers that report cost (OpenRouter) count towards it."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Input
+          type="number"
+          min={0}
+          step={0.5}
+          value={localValue}
+          onChange={(e) => setLocalValue(e.target.value)}
+          onBlur={handleBlur}
+          placeholder="No limit"
+          className="w-32"
+        />
+      </SettingContainer>
+    );
+  },
+);
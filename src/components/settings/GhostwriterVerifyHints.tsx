@@ -0,0 +1,25 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface GhostwriterVerifyHintsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const GhostwriterVerifyHints: React.FC<GhostwriterVerifyHintsProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("ghostwriter_verify_hints") ?? false}
+        onChange={(enabled) => updateSetting("ghostwriter_verify_hints", enabled)}
+        isUpdating={isUpdating("ghostwriter_verify_hints")}
+        label="Verify Uncertain Words"
+        description="Tell the ghostwriter which words speech recognition was unsure about so it can check them against the context."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
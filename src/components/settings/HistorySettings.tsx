@@ -9,6 +9,7 @@ import { Input } from "../ui/Input";
 import { Button } from "../ui/Button";
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "../ui/Select";
 import { useSettings } from "../../hooks/useSettings";
+import { TranscriptionJobs } from "./TranscriptionJobs";
 
 interface HistoryEntry {
   id: number;
@@ -256,6 +257,9 @@ export const HistorySettings: React.FC = () => {
         </p>
       </div>
 
+      {/* Background imports of dropped audio files */}
+      <TranscriptionJobs />
+
       {/* Search Bar */}
       <div className="relative">
         <div className="absolute left-3 top-1/2 -translate-y-1/2 text-neutral-400">
@@ -347,7 +351,7 @@ export const HistorySettings: React.FC = () => {
               </div>
 
               {/* Profile Filter - Only show in ghostwriter mode */}
-              {getSetting("output_mode") === "ghostwriter" && (
+              {(getSetting("output_mode") === "ghostwriter" || getSetting("output_mode") === "reply") && (
                 <div>
                   <label className="block text-sm font-medium text-foreground mb-2">
                     Profile
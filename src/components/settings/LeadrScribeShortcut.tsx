@@ -33,6 +33,12 @@ export const LeadrScribeShortcut: React.FC<LeadrScribeShortcutProps> = ({
   const shortcutRefs = useRef<Map<string, HTMLDivElement | null>>(new Map());
 
   const bindings = getSetting("bindings") || {};
+  // Stable order: the main transcribe shortcut first, then the rest by name
+  const orderedBindingIds = Object.keys(bindings).sort((a, b) => {
+    if (a === "transcribe") return -1;
+    if (b === "transcribe") return 1;
+    return bindings[a].name.localeCompare(bindings[b].name);
+  });
 
   // Detect and store OS type
   useEffect(() => {
@@ -277,46 +283,48 @@ export const LeadrScribeShortcut: React.FC<LeadrScribeShortcutProps> = ({
 
   return (
     <SettingContainer
-      title="LeadrScribe Shortcut"
-      description="Set the keyboard shortcut to start and stop speech-to-text recording"
+      title="LeadrScribe Shortcuts"
+      description="Set the keyboard shortcuts to start and stop speech-to-text recording. Transform Selection rewrites the selected text using what you say."
       descriptionMode={descriptionMode}
       grouped={grouped}
       tooltipPosition="bottom"
     >
-      {(() => {
-        const primaryBinding = Object.values(bindings)[0];
-        const primaryId = Object.keys(bindings)[0];
-
-        if (!primaryBinding) {
+      <div className="space-y-2">
+        {orderedBindingIds.map((id) => {
+          const binding = bindings[id];
           return (
-            <div className="text-sm text-muted-foreground">No shortcuts configured</div>
+            <div key={id} className="flex items-center justify-end space-x-1">
+              {orderedBindingIds.length > 1 && (
+                <span
+                  className="text-xs text-muted-foreground mr-2"
+                  title={binding.description}
+                >
+                  {binding.name}
+                </span>
+              )}
+              {editingShortcutId === id ? (
+                <div
+                  ref={(ref) => setShortcutRef(id, ref)}
+                  className="px-2 py-1 text-sm font-semibold border border-primary bg-primary/30 rounded min-w-[120px] text-center"
+                >
+                  {formatCurrentKeys()}
+                </div>
+              ) : (
+                <div
+                  className="px-2 py-1 text-sm font-semibold bg-muted border border-border hover:bg-primary/10 rounded cursor-pointer hover:border-primary"
+                  onClick={() => startRecording(id)}
+                >
+                  {formatKeyCombination(binding.current_binding, osType)}
+                </div>
+              )}
+              <ResetButton
+                onClick={() => resetBinding(id)}
+                disabled={isUpdating(`binding_${id}`)}
+              />
+            </div>
           );
-        }
-
-        return (
-          <div className="flex items-center space-x-1">
-            {editingShortcutId === primaryId ? (
-              <div
-                ref={(ref) => setShortcutRef(primaryId, ref)}
-                className="px-2 py-1 text-sm font-semibold border border-primary bg-primary/30 rounded min-w-[120px] text-center"
-              >
-                {formatCurrentKeys()}
-              </div>
-            ) : (
-              <div
-                className="px-2 py-1 text-sm font-semibold bg-muted border border-border hover:bg-primary/10 rounded cursor-pointer hover:border-primary"
-                onClick={() => startRecording(primaryId)}
-              >
-                {formatKeyCombination(primaryBinding.current_binding, osType)}
-              </div>
-            )}
-            <ResetButton
-              onClick={() => resetBinding(primaryId)}
-              disabled={isUpdating(`binding_${primaryId}`)}
-            />
-          </div>
-        );
-      })()}
+        })}
+      </div>
     </SettingContainer>
   );
 };
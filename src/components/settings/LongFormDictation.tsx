@@ -0,0 +1,26 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface LongFormDictationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const LongFormDictation: React.FC<LongFormDictationProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <ToggleSwitch
+        checked={getSetting("long_form_dictation") ?? false}
+        onChange={(enabled) => updateSetting("long_form_dictation", enabled)}
+        isUpdating={isUpdating("long_form_dictation")}
+        label="Long-Form Dictation"
+        description="For recordings of many minutes or hours. Audio is written to disk while you speak and transcribed in chunks at pauses, so memory use stays flat and the text is ready soon after you stop."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
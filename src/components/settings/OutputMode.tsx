@@ -12,6 +12,7 @@ interface OutputModeProps {
 const outputModeOptions = [
   { value: "transcript", label: "Transcript" },
   { value: "ghostwriter", label: "Ghost mode" },
+  { value: "reply", label: "Reply mode" },
 ];
 
 export const OutputModeSetting: React.FC<OutputModeProps> = React.memo(
@@ -24,7 +25,7 @@ export const OutputModeSetting: React.FC<OutputModeProps> = React.memo(
     return (
       <SettingContainer
         title="Output Mode"
-        description="Transcript provides raw speech-to-text output. Ghost mode rewrites the transcription using AI based on your custom instructions (requires OpenRouter API key)."
+        description="Transcript provides raw speech-to-text output. Ghost mode rewrites the transcription using AI based on your custom instructions (requires OpenRouter API key). Reply mode does the same, using the selected text (or clipboard) as the message you are replying to."
         descriptionMode={descriptionMode}
         grouped={grouped}
         tooltipPosition="bottom"
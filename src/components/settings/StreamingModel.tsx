@@ -0,0 +1,57 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface StreamingModelProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+interface ModelInfo {
+  id: string;
+  name: string;
+  is_downloaded: boolean;
+}
+
+// Empty value means "stream with the transcription model"
+const SAME_MODEL = "";
+
+export const StreamingModel: React.FC<StreamingModelProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const [models, setModels] = useState<ModelInfo[]>([]);
+
+  useEffect(() => {
+    invoke<ModelInfo[]>("get_available_models")
+      .then(setModels)
+      .catch((error) => console.error("Failed to load models:", error));
+  }, []);
+
+  const selectedModel = getSetting("selected_model");
+  const options = [
+    { value: SAME_MODEL, label: "Same as transcription model" },
+    ...models
+      .filter((model) => model.is_downloaded && model.id !== selectedModel)
+      .map((model) => ({ value: model.id, label: model.name })),
+  ];
+
+  return (
+    <SettingContainer
+      title="Streaming Model"
+      description="A faster model for the live preview while you speak. When set, your transcription model re-transcribes the whole recording at the end and its text is what gets pasted. Both models stay in memory during recording."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    >
+      <Dropdown
+        options={options}
+        selectedValue={getSetting("streaming_model") ?? SAME_MODEL}
+        onSelect={(value) => updateSetting("streaming_model", value || null)}
+        disabled={isUpdating("streaming_model")}
+      />
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,125 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { X } from "lucide-react";
+import { Button } from "../ui/Button";
+
+interface TranscriptionJob {
+  id: number;
+  file_path: string;
+  status: "queued" | "running" | "completed" | "failed" | "cancelled";
+  progress: number;
+  history_id: number | null;
+  error: string | null;
+  created_at: number;
+  updated_at: number;
+}
+
+const STATUS_LABELS: Record<TranscriptionJob["status"], string> = {
+  queued: "Queued",
+  running: "Transcribing",
+  completed: "Done",
+  failed: "Failed",
+  cancelled: "Cancelled",
+};
+
+// Background transcription of dropped files and folders
+export const TranscriptionJobs: React.FC = () => {
+  const [jobs, setJobs] = useState<TranscriptionJob[]>([]);
+
+  const loadJobs = async () => {
+    try {
+      setJobs(await invoke<TranscriptionJob[]>("get_transcription_jobs"));
+    } catch (error) {
+      console.error("Failed to load transcription jobs:", error);
+    }
+  };
+
+  useEffect(() => {
+    loadJobs();
+
+    const unlistenUpdated = listen<TranscriptionJob>("transcription-job-updated", (event) => {
+      const updated = event.payload;
+      setJobs((current) =>
+        current.some((job) => job.id === updated.id)
+          ? current.map((job) => (job.id === updated.id ? updated : job))
+          : [updated, ...current]
+      );
+    });
+    const unlistenCleared = listen("transcription-jobs-cleared", loadJobs);
+
+    return () => {
+      unlistenUpdated.then((fn) => fn());
+      unlistenCleared.then((fn) => fn());
+    };
+  }, []);
+
+  if (jobs.length === 0) return null;
+
+  const isActive = (job: TranscriptionJob) =>
+    job.status === "queued" || job.status === "running";
+
+  const handleCancel = async (id: number) => {
+    try {
+      await invoke("cancel_transcription_job", { id });
+    } catch (error) {
+      console.error("Failed to cancel transcription job:", error);
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      await invoke("clear_finished_transcription_jobs");
+    } catch (error) {
+      console.error("Failed to clear transcription jobs:", error);
+    }
+  };
+
+  return (
+    <div className="bg-card rounded-xl p-4 space-y-3 border border-border/30 shadow-sm">
+      <div className="flex items-center justify-between">
+        <h3 className="font-semibold text-foreground">Imports</h3>
+        {jobs.some((job) => !isActive(job)) && (
+          <Button variant="ghost" size="sm" onClick={handleClear}>
+            Clear finished
+          </Button>
+        )}
+      </div>
+      {jobs.map((job) => (
+        <div key={job.id} className="space-y-1">
+          <div className="flex items-center justify-between gap-2 text-sm">
+            <span className="truncate text-foreground" title={job.file_path}>
+              {job.file_path.split(/[\\/]/).pop()}
+            </span>
+            <div className="flex items-center gap-2 shrink-0 text-muted-foreground">
+              <span title={job.error ?? undefined}>
+                {STATUS_LABELS[job.status]}
+                {job.status === "running" && ` ${Math.round(job.progress * 100)}%`}
+              </span>
+              {isActive(job) && (
+                <button
+                  onClick={() => handleCancel(job.id)}
+                  className="p-1 rounded hover:bg-muted transition-colors"
+                  title="Cancel"
+                >
+                  <X className="w-4 h-4" />
+                </button>
+              )}
+            </div>
+          </div>
+          {job.status === "running" && (
+            <div className="h-1 bg-muted rounded-full overflow-hidden">
+              <div
+                className="h-full bg-primary transition-all"
+                style={{ width: `${job.progress * 100}%` }}
+              />
+            </div>
+          )}
+          {job.status === "failed" && job.error && (
+            <p className="text-xs text-destructive">{job.error}</p>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+};
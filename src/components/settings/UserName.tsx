@@ -0,0 +1,69 @@
+import React, { useState, useEffect, useRef } from "react";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettingsStore } from "../../stores/settingsStore";
+
+interface UserNameProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const UserName: React.FC<UserNameProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const [localValue, setLocalValue] = useState("");
+    const timeoutRef = useRef<NodeJS.Timeout | null>(null);
+
+    // Load initial value only once on mount
+    useEffect(() => {
+      const settings = useSettingsStore.getState().settings;
+      setLocalValue((settings?.user_name || "") as string);
+    }, []);
+
+    // Cleanup timeout on unmount
+    useEffect(() => {
+      return () => {
+        if (timeoutRef.current) {
+          clearTimeout(timeoutRef.current);
+        }
+      };
+    }, []);
+
+    const handleChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+      const value = e.target.value;
+      setLocalValue(value);
+
+      // Debounce backend updates
+      if (timeoutRef.current) {
+        clearTimeout(timeoutRef.current);
+      }
+      timeoutRef.current = setTimeout(() => {
+        useSettingsStore.getState().updateSetting("user_name", value);
+      }, 500);
+    };
+
+    const handleBlur = () => {
+      // Save immediately on blur
+      if (timeoutRef.current) {
+        clearTimeout(timeoutRef.current);
+      }
+      useSettingsStore.getState().updateSetting("user_name", localValue);
+    };
+
+    return (
+      <SettingContainer
+        title="Your Name"
+        description="Used for {user_name} in custom instructions, e.g. to sign emails."
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Input
+          value={localValue}
+          onChange={handleChange}
+          onBlur={handleBlur}
+          placeholder="Your name"
+          className="w-48"
+        />
+      </SettingContainer>
+    );
+  },
+);
@@ -0,0 +1,28 @@
+import React from "react";
+import { Slider } from "../../ui/Slider";
+import { useSettings } from "../../../hooks/useSettings";
+
+interface LowConfidenceThresholdProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const LowConfidenceThreshold: React.FC<LowConfidenceThresholdProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { settings, updateSetting } = useSettings();
+
+  return (
+    <Slider
+      value={settings?.low_confidence_threshold ?? 0.5}
+      onChange={(value) => updateSetting("low_confidence_threshold", value)}
+      min={0.0}
+      max={1.0}
+      label="Low Confidence Threshold"
+      description="Words recognised with less confidence than this are underlined in the overlay and in history. Only applies when the speech engine reports word confidence."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    />
+  );
+};
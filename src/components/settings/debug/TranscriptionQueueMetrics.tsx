@@ -0,0 +1,69 @@
+import React from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { SettingContainer } from "../../ui/SettingContainer";
+
+interface PriorityMetrics {
+  priority: "final" | "import" | "partial";
+  started: number;
+  cancelled: number;
+  total_wait_ms: number;
+  max_wait_ms: number;
+  last_wait_ms: number;
+}
+
+interface QueueMetrics {
+  pending: number;
+  priorities: PriorityMetrics[];
+}
+
+const PRIORITY_LABELS: Record<PriorityMetrics["priority"], string> = {
+  final: "Final pass",
+  import: "Imports",
+  partial: "Streaming",
+};
+
+interface TranscriptionQueueMetricsProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const TranscriptionQueueMetrics: React.FC<
+  TranscriptionQueueMetricsProps
+> = ({ descriptionMode = "tooltip", grouped = false }) => {
+  const [metrics, setMetrics] = React.useState<QueueMetrics | null>(null);
+
+  React.useEffect(() => {
+    const load = () =>
+      invoke<QueueMetrics>("get_transcription_queue_metrics")
+        .then(setMetrics)
+        .catch((error) => console.error("Failed to load queue metrics:", error));
+    load();
+    const interval = setInterval(load, 2000);
+    return () => clearInterval(interval);
+  }, []);
+
+  return (
+    <SettingContainer
+      title="Transcription Queue"
+      description="How long transcription jobs waited for the model, by priority. Stale streaming windows are skipped before they start."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      {metrics && (
+        <div className="text-sm text-foreground space-y-1">
+          <div className="text-xs text-muted-foreground">{metrics.pending} waiting</div>
+          {metrics.priorities.map((m) => (
+            <div key={m.priority} className="flex justify-between font-mono text-xs">
+              <span>{PRIORITY_LABELS[m.priority]}</span>
+              <span>
+                {m.started} run, {m.cancelled} skipped · avg{" "}
+                {m.started ? Math.round(m.total_wait_ms / m.started) : 0}ms · max {m.max_wait_ms}ms
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+    </SettingContainer>
+  );
+};
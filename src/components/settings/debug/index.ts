@@ -1 +1,3 @@
 export { WordCorrectionThreshold } from "./WordCorrectionThreshold";
+export { TranscriptionQueueMetrics } from "./TranscriptionQueueMetrics";
+export { LowConfidenceThreshold } from "./LowConfidenceThreshold";
@@ -1,212 +1,243 @@
-import React, { useState, useRef, useEffect, useCallback } from "react";
+import React, {
+  useState,
+  useRef,
+  useEffect,
+  useCallback,
+  forwardRef,
+  useImperativeHandle,
+} from "react";
 import { Play, Pause } from "lucide-react";
 import { cn } from "../../lib/utils";
 
 interface AudioPlayerProps {
   src: string;
   className?: string;
+  onTimeUpdate?: (time: number) => void;
 }
 
-export const AudioPlayer: React.FC<AudioPlayerProps> = ({
-  src,
-  className = "",
-}) => {
-  const [isPlaying, setIsPlaying] = useState(false);
-  const [duration, setDuration] = useState(0);
-  const [currentTime, setCurrentTime] = useState(0);
-  const [isDragging, setIsDragging] = useState(false);
-
-  const audioRef = useRef<HTMLAudioElement>(null);
-  const animationRef = useRef<number>();
-  const dragTimeRef = useRef<number>(0);
-
-  const isPlayingRef = useRef(false);
-  const isDraggingRef = useRef(false);
-
-  useEffect(() => {
-    isPlayingRef.current = isPlaying;
-  }, [isPlaying]);
-
-  useEffect(() => {
-    isDraggingRef.current = isDragging;
-  }, [isDragging]);
-
-  const tick = useCallback(() => {
-    if (audioRef.current && !isDraggingRef.current) {
-      const time = audioRef.current.currentTime;
-      setCurrentTime(time);
-    }
-
-    if (isPlayingRef.current) {
-      animationRef.current = requestAnimationFrame(tick);
-    }
-  }, []);
-
-  useEffect(() => {
-    if (isPlaying && !isDragging) {
-      if (!animationRef.current) {
+export interface AudioPlayerHandle {
+  // Jump to `time` (seconds) and start playing
+  seek: (time: number) => void;
+}
+
+export const AudioPlayer = forwardRef<AudioPlayerHandle, AudioPlayerProps>(
+  ({ src, className = "", onTimeUpdate }, ref) => {
+    const [isPlaying, setIsPlaying] = useState(false);
+    const [duration, setDuration] = useState(0);
+    const [currentTime, setCurrentTime] = useState(0);
+    const [isDragging, setIsDragging] = useState(false);
+
+    const audioRef = useRef<HTMLAudioElement>(null);
+    const animationRef = useRef<number>();
+    const dragTimeRef = useRef<number>(0);
+
+    const isPlayingRef = useRef(false);
+    const isDraggingRef = useRef(false);
+    const onTimeUpdateRef = useRef(onTimeUpdate);
+
+    useEffect(() => {
+      onTimeUpdateRef.current = onTimeUpdate;
+    }, [onTimeUpdate]);
+
+    useImperativeHandle(ref, () => ({
+      seek: (time: number) => {
+        const audio = audioRef.current;
+        if (!audio) return;
+        audio.currentTime = time;
+        setCurrentTime(time);
+        onTimeUpdateRef.current?.(time);
+        audio.play().catch((error) => console.error("Playback failed:", error));
+      },
+    }));
+
+    useEffect(() => {
+      isPlayingRef.current = isPlaying;
+    }, [isPlaying]);
+
+    useEffect(() => {
+      isDraggingRef.current = isDragging;
+    }, [isDragging]);
+
+    const tick = useCallback(() => {
+      if (audioRef.current && !isDraggingRef.current) {
+        const time = audioRef.current.currentTime;
+        setCurrentTime(time);
+        onTimeUpdateRef.current?.(time);
+      }
+
+      if (isPlayingRef.current) {
         animationRef.current = requestAnimationFrame(tick);
       }
-    } else {
-      if (animationRef.current) {
-        cancelAnimationFrame(animationRef.current);
-        animationRef.current = undefined;
+    }, []);
+
+    useEffect(() => {
+      if (isPlaying && !isDragging) {
+        if (!animationRef.current) {
+          animationRef.current = requestAnimationFrame(tick);
+        }
+      } else {
+        if (animationRef.current) {
+          cancelAnimationFrame(animationRef.current);
+          animationRef.current = undefined;
+        }
+      }
+
+      return () => {
+        if (animationRef.current) {
+          cancelAnimationFrame(animationRef.current);
+          animationRef.current = undefined;
+        }
+      };
+    }, [isPlaying, isDragging, tick]);
+
+    useEffect(() => {
+      const audio = audioRef.current;
+      if (!audio) return;
+
+      const handleLoadedMetadata = () => {
+        setDuration(audio.duration || 0);
+        setCurrentTime(0);
+      };
+
+      const handleEnded = () => {
+        setIsPlaying(false);
+        setCurrentTime(audio.duration || 0);
+      };
+
+      const handlePlay = () => setIsPlaying(true);
+      const handlePause = () => setIsPlaying(false);
+
+      audio.addEventListener("loadedmetadata", handleLoadedMetadata);
+      audio.addEventListener("ended", handleEnded);
+      audio.addEventListener("play", handlePlay);
+      audio.addEventListener("pause", handlePause);
+
+      return () => {
+        audio.removeEventListener("loadedmetadata", handleLoadedMetadata);
+        audio.removeEventListener("ended", handleEnded);
+        audio.removeEventListener("play", handlePlay);
+        audio.removeEventListener("pause", handlePause);
+      };
+    }, []);
+
+    const handleMouseUp = useCallback(() => {
+      if (isDragging) {
+        setIsDragging(false);
+        if (audioRef.current) {
+          audioRef.current.currentTime = dragTimeRef.current;
+          setCurrentTime(dragTimeRef.current);
+        }
       }
-    }
+    }, [isDragging]);
 
-    return () => {
-      if (animationRef.current) {
-        cancelAnimationFrame(animationRef.current);
-        animationRef.current = undefined;
+    useEffect(() => {
+      if (isDragging) {
+        document.addEventListener("mouseup", handleMouseUp);
+        document.addEventListener("touchend", handleMouseUp);
+
+        return () => {
+          document.removeEventListener("mouseup", handleMouseUp);
+          document.removeEventListener("touchend", handleMouseUp);
+        };
+      }
+    }, [isDragging, handleMouseUp]);
+
+    const togglePlay = async () => {
+      const audio = audioRef.current;
+      if (!audio) return;
+
+      try {
+        if (isPlaying) {
+          audio.pause();
+        } else {
+          await audio.play();
+        }
+      } catch (error) {
+        console.error("Playback failed:", error);
       }
     };
-  }, [isPlaying, isDragging, tick]);
 
-  useEffect(() => {
-    const audio = audioRef.current;
-    if (!audio) return;
+    const handleSeek = (e: React.ChangeEvent<HTMLInputElement>) => {
+      const newTime = parseFloat(e.target.value);
+      dragTimeRef.current = newTime;
+      setCurrentTime(newTime);
 
-    const handleLoadedMetadata = () => {
-      setDuration(audio.duration || 0);
-      setCurrentTime(0);
+      if (!isDragging && audioRef.current) {
+        audioRef.current.currentTime = newTime;
+      }
     };
 
-    const handleEnded = () => {
-      setIsPlaying(false);
-      setCurrentTime(audio.duration || 0);
+    const handleSliderMouseDown = () => {
+      setIsDragging(true);
     };
 
-    const handlePlay = () => setIsPlaying(true);
-    const handlePause = () => setIsPlaying(false);
-
-    audio.addEventListener("loadedmetadata", handleLoadedMetadata);
-    audio.addEventListener("ended", handleEnded);
-    audio.addEventListener("play", handlePlay);
-    audio.addEventListener("pause", handlePause);
-
-    return () => {
-      audio.removeEventListener("loadedmetadata", handleLoadedMetadata);
-      audio.removeEventListener("ended", handleEnded);
-      audio.removeEventListener("play", handlePlay);
-      audio.removeEventListener("pause", handlePause);
+    const handleSliderTouchStart = () => {
+      setIsDragging(true);
     };
-  }, []);
-
-  const handleMouseUp = useCallback(() => {
-    if (isDragging) {
-      setIsDragging(false);
-      if (audioRef.current) {
-        audioRef.current.currentTime = dragTimeRef.current;
-        setCurrentTime(dragTimeRef.current);
-      }
-    }
-  }, [isDragging]);
 
-  useEffect(() => {
-    if (isDragging) {
-      document.addEventListener("mouseup", handleMouseUp);
-      document.addEventListener("touchend", handleMouseUp);
+    const formatTime = (time: number): string => {
+      if (!isFinite(time)) return "0:00";
 
-      return () => {
-        document.removeEventListener("mouseup", handleMouseUp);
-        document.removeEventListener("touchend", handleMouseUp);
-      };
-    }
-  }, [isDragging, handleMouseUp]);
+      const minutes = Math.floor(time / 60);
+      const seconds = Math.floor(time % 60);
+      return `${minutes}:${seconds.toString().padStart(2, "0")}`;
+    };
 
-  const togglePlay = async () => {
-    const audio = audioRef.current;
-    if (!audio) return;
+    const getProgressPercent = (): number => {
+      if (duration <= 0) return 0;
+      if (duration - currentTime < 0.1) return 100;
+      const percent = (currentTime / duration) * 100;
+      return Math.min(100, Math.max(0, percent));
+    };
 
-    try {
-      if (isPlaying) {
-        audio.pause();
-      } else {
-        await audio.play();
-      }
-    } catch (error) {
-      console.error("Playback failed:", error);
-    }
-  };
-
-  const handleSeek = (e: React.ChangeEvent<HTMLInputElement>) => {
-    const newTime = parseFloat(e.target.value);
-    dragTimeRef.current = newTime;
-    setCurrentTime(newTime);
-
-    if (!isDragging && audioRef.current) {
-      audioRef.current.currentTime = newTime;
-    }
-  };
-
-  const handleSliderMouseDown = () => {
-    setIsDragging(true);
-  };
-
-  const handleSliderTouchStart = () => {
-    setIsDragging(true);
-  };
-
-  const formatTime = (time: number): string => {
-    if (!isFinite(time)) return "0:00";
-
-    const minutes = Math.floor(time / 60);
-    const seconds = Math.floor(time % 60);
-    return `${minutes}:${seconds.toString().padStart(2, "0")}`;
-  };
-
-  const getProgressPercent = (): number => {
-    if (duration <= 0) return 0;
-    if (duration - currentTime < 0.1) return 100;
-    const percent = (currentTime / duration) * 100;
-    return Math.min(100, Math.max(0, percent));
-  };
-
-  const progressPercent = getProgressPercent();
-
-  return (
-    <div className={cn("flex items-center gap-3", className)}>
-      <audio ref={audioRef} src={src} preload="metadata" />
-
-      <button
-        onClick={togglePlay}
-        className="transition-colors cursor-pointer text-foreground hover:text-primary"
-        aria-label={isPlaying ? "Pause" : "Play"}
-      >
-        {isPlaying ? (
-          <Pause width={20} height={20} fill="currentColor" />
-        ) : (
-          <Play width={20} height={20} fill="currentColor" />
-        )}
-      </button>
-
-      <div className="flex-1 flex items-center gap-2">
-        <span className="text-xs text-muted-foreground min-w-[30px] tabular-nums">
-          {formatTime(currentTime)}
-        </span>
-
-        <div className="flex-1 relative h-2 bg-muted rounded-full border border-border/30">
-          <div
-            className="absolute inset-y-0 left-0 bg-primary rounded-full"
-            style={{ width: `${progressPercent}%` }}
-          />
-          <input
-            type="range"
-            min="0"
-            max={duration || 0}
-            step="0.01"
-            value={currentTime}
-            onChange={handleSeek}
-            onMouseDown={handleSliderMouseDown}
-            onTouchStart={handleSliderTouchStart}
-            className="absolute inset-0 w-full h-full opacity-0 cursor-pointer"
-          />
+    const progressPercent = getProgressPercent();
+
+    return (
+      <div className={cn("flex items-center gap-3", className)}>
+        <audio ref={audioRef} src={src} preload="metadata" />
+
+        <button
+          onClick={togglePlay}
+          className="transition-colors cursor-pointer text-foreground hover:text-primary"
+          aria-label={isPlaying ? "Pause" : "Play"}
+        >
+          {isPlaying ? (
+            <Pause width={20} height={20} fill="currentColor" />
+          ) : (
+            <Play width={20} height={20} fill="currentColor" />
+          )}
+        </button>
+
+        <div className="flex-1 flex items-center gap-2">
+          <span className="text-xs text-muted-foreground min-w-[30px] tabular-nums">
+            {formatTime(currentTime)}
+          </span>
+
+          <div className="flex-1 relative h-2 bg-muted rounded-full border border-border/30">
+            <div
+              className="absolute inset-y-0 left-0 bg-primary rounded-full"
+              style={{ width: `${progressPercent}%` }}
+            />
+            <input
+              type="range"
+              min="0"
+              max={duration || 0}
+              step="0.01"
+              value={currentTime}
+              onChange={handleSeek}
+              onMouseDown={handleSliderMouseDown}
+              onTouchStart={handleSliderTouchStart}
+              className="absolute inset-0 w-full h-full opacity-0 cursor-pointer"
+            />
+          </div>
+
+          <span className="text-xs text-muted-foreground min-w-[30px] tabular-nums">
+            {formatTime(duration)}
+          </span>
         </div>
-
-        <span className="text-xs text-muted-foreground min-w-[30px] tabular-nums">
-          {formatTime(duration)}
-        </span>
       </div>
-    </div>
-  );
-};
+    );
+  },
+);
+
+AudioPlayer.displayName = "AudioPlayer";
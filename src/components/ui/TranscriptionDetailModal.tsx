@@ -1,10 +1,15 @@
 import React from "react";
 import { motion, AnimatePresence } from "framer-motion";
-import { X, Copy, Check, Star, Clock, Calendar } from "lucide-react";
+import { X, Copy, Check, Star, Clock, Calendar, Captions } from "lucide-react";
 import { Button } from "./Button";
-import { AudioPlayer } from "./AudioPlayer";
+import { AudioPlayer, AudioPlayerHandle } from "./AudioPlayer";
+import { TranscriptionVersions } from "./TranscriptionVersions";
 import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { toast } from "sonner";
 import { cn } from "../../lib/utils";
+import { LANGUAGES } from "../../lib/constants/languages";
+import { useSettings } from "../../hooks/useSettings";
 
 interface HistoryEntry {
   id: number;
@@ -18,6 +23,26 @@ interface HistoryEntry {
   notes: string | null;
   duration_seconds: number | null;
   word_count: number | null;
+  ghostwriter_model?: string | null;
+  prompt_tokens?: number | null;
+  completion_tokens?: number | null;
+  ghostwriter_cost?: number | null;
+  language?: string | null;
+  language_probability?: number | null;
+}
+
+interface WordTiming {
+  text: string;
+  start: number;
+  end: number;
+  confidence?: number;
+}
+
+interface TranscriptSegment {
+  text: string;
+  start: number;
+  end: number;
+  words: WordTiming[];
 }
 
 interface TranscriptionDetailModalProps {
@@ -36,13 +61,52 @@ export const TranscriptionDetailModal: React.FC<TranscriptionDetailModalProps> =
   const [copiedOriginal, setCopiedOriginal] = React.useState(false);
   const [copiedGhostwritten, setCopiedGhostwritten] = React.useState(false);
   const [audioUrl, setAudioUrl] = React.useState<string | null>(null);
+  const [segments, setSegments] = React.useState<TranscriptSegment[]>([]);
+  const [playbackTime, setPlaybackTime] = React.useState<number | null>(null);
+  const [exportingFormat, setExportingFormat] = React.useState<"srt" | "vtt" | null>(null);
+  const playerRef = React.useRef<AudioPlayerHandle>(null);
+  const { getSetting } = useSettings();
+  const lowConfidenceThreshold = getSetting("low_confidence_threshold") ?? 0.5;
 
   React.useEffect(() => {
     if (entry && isOpen) {
       loadAudioUrl();
+      loadSegments();
     }
   }, [entry, isOpen]);
 
+  const loadSegments = async () => {
+    if (!entry) return;
+    setPlaybackTime(null);
+    try {
+      const data = await invoke<TranscriptSegment[]>("get_transcription_segments", {
+        historyId: entry.id,
+      });
+      setSegments(data);
+    } catch (error) {
+      console.error("Failed to load transcription segments:", error);
+      setSegments([]);
+    }
+  };
+
+  const handleExportSubtitles = async (format: "srt" | "vtt") => {
+    if (!entry) return;
+    setExportingFormat(format);
+    try {
+      const path = await invoke<string>("export_subtitles", {
+        historyId: entry.id,
+        format,
+      });
+      toast(`Saved ${path.split(/[\\/]/).pop()}`, { description: "Subtitles Exported" });
+      revealItemInDir(path).catch((error) => console.error("Failed to reveal subtitles:", error));
+      loadSegments();
+    } catch (error) {
+      toast.error(String(error), { description: "Subtitle Export Failed" });
+    } finally {
+      setExportingFormat(null);
+    }
+  };
+
   const loadAudioUrl = async () => {
     if (!entry) return;
     try {
@@ -91,6 +155,12 @@ export const TranscriptionDetailModal: React.FC<TranscriptionDetailModalProps> =
     return `${mins}:${secs.toString().padStart(2, "0")}`;
   };
 
+  const formatOffset = (seconds: number): string => {
+    const mins = Math.floor(seconds / 60);
+    const secs = Math.floor(seconds % 60);
+    return `${mins}:${secs.toString().padStart(2, "0")}`;
+  };
+
   const hasGhostwritten = entry.ghostwritten_text && entry.ghostwritten_text.trim().length > 0;
 
   return (
@@ -136,6 +206,25 @@ export const TranscriptionDetailModal: React.FC<TranscriptionDetailModalProps> =
                       {entry.word_count} words
                     </span>
                   )}
+                  {entry.language && (
+                    <span title="Language the transcription was dictated in">
+                      {LANGUAGES.find((l) => l.value === entry.language)?.label ?? entry.language}
+                      {entry.language_probability != null &&
+                        ` (${Math.round(entry.language_probability * 100)}%)`}
+                    </span>
+                  )}
+                  {entry.ghostwriter_model && (
+                    <span title="Model that wrote the ghostwritten text">
+                      {entry.ghostwriter_model}
+                    </span>
+                  )}
+                  {entry.prompt_tokens != null && entry.completion_tokens != null && (
+                    <span title="Prompt + completion tokens used for ghostwriting">
+                      {entry.prompt_tokens + entry.completion_tokens} tokens
+                      {entry.ghostwriter_cost != null &&
+                        ` · $${entry.ghostwriter_cost.toFixed(4)}`}
+                    </span>
+                  )}
                 </div>
               </div>
               <div className="flex items-center gap-1">
@@ -167,10 +256,72 @@ export const TranscriptionDetailModal: React.FC<TranscriptionDetailModalProps> =
               {/* Audio Player */}
               {audioUrl && (
                 <div>
-                  <h3 className="text-sm font-semibold text-foreground uppercase tracking-wide mb-3">
-                    Recording
-                  </h3>
-                  <AudioPlayer src={audioUrl} className="w-full" />
+                  <div className="flex items-center justify-between mb-3">
+                    <h3 className="text-sm font-semibold text-foreground uppercase tracking-wide">
+                      Recording
+                    </h3>
+                    <div className="flex items-center gap-2">
+                      {(["srt", "vtt"] as const).map((format) => (
+                        <Button
+                          key={format}
+                          variant="secondary"
+                          size="sm"
+                          onClick={() => handleExportSubtitles(format)}
+                          disabled={exportingFormat !== null}
+                          className="flex items-center gap-2"
+                          title={`Export ${format.toUpperCase()} subtitles to Downloads`}
+                        >
+                          <Captions className="w-4 h-4" />
+                          {exportingFormat === format ? "Exporting..." : format.toUpperCase()}
+                        </Button>
+                      ))}
+                    </div>
+                  </div>
+                  <AudioPlayer
+                    ref={playerRef}
+                    src={audioUrl}
+                    className="w-full"
+                    onTimeUpdate={setPlaybackTime}
+                  />
+                  {segments.length > 0 && (
+                    // Click a word to play the recording from there
+                    <div className="mt-3 space-y-1 text-sm leading-relaxed">
+                      {segments.map((segment, segmentIndex) => (
+                        <p key={segmentIndex} className="text-foreground">
+                          <span className="mr-2 text-xs text-muted-foreground tabular-nums">
+                            {formatOffset(segment.start)}
+                          </span>
+                          {segment.words.map((word, wordIndex) => {
+                            const isCurrent =
+                              playbackTime !== null &&
+                              playbackTime >= word.start &&
+                              playbackTime < word.end;
+                            const isUncertain =
+                              word.confidence != null &&
+                              word.confidence < lowConfidenceThreshold;
+                            return (
+                              <span
+                                key={wordIndex}
+                                onClick={() => playerRef.current?.seek(word.start)}
+                                className={cn(
+                                  "cursor-pointer rounded hover:bg-primary/20",
+                                  isCurrent && "bg-primary/30",
+                                  isUncertain && "underline decoration-dotted decoration-destructive"
+                                )}
+                                title={
+                                  word.confidence != null
+                                    ? `${Math.round(word.confidence * 100)}% confidence`
+                                    : undefined
+                                }
+                              >
+                                {word.text}{" "}
+                              </span>
+                            );
+                          })}
+                        </p>
+                      ))}
+                    </div>
+                  )}
                 </div>
               )}
 
@@ -206,6 +357,9 @@ export const TranscriptionDetailModal: React.FC<TranscriptionDetailModalProps> =
                 </div>
               </div>
 
+              {/* Alternative transcripts from other models */}
+              {audioUrl && <TranscriptionVersions historyId={entry.id} />}
+
               {/* Ghostwritten Version */}
               {hasGhostwritten && (
                 <div>
@@ -0,0 +1,180 @@
+import React from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { RefreshCw, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "./Button";
+import { Dropdown } from "./Dropdown";
+import { LANGUAGES } from "../../lib/constants/languages";
+import { ModelInfo } from "../../lib/types";
+import { cn } from "../../lib/utils";
+
+interface TranscriptionVersion {
+  id: number;
+  history_id: number;
+  model_id: string;
+  language: string | null;
+  translate: boolean;
+  transcription_text: string;
+  created_at: number;
+}
+
+interface DiffChunk {
+  op: "equal" | "insert" | "delete";
+  text: string;
+}
+
+interface VersionComparison {
+  version: TranscriptionVersion;
+  diff: DiffChunk[];
+}
+
+interface TranscriptionVersionsProps {
+  historyId: number;
+}
+
+// Re-runs a recording through another downloaded model and diffs the result against the original
+export const TranscriptionVersions: React.FC<TranscriptionVersionsProps> = ({ historyId }) => {
+  const [models, setModels] = React.useState<ModelInfo[]>([]);
+  const [versions, setVersions] = React.useState<VersionComparison[]>([]);
+  const [modelId, setModelId] = React.useState<string | null>(null);
+  const [language, setLanguage] = React.useState("auto");
+  const [translate, setTranslate] = React.useState(false);
+  const [isRunning, setIsRunning] = React.useState(false);
+
+  React.useEffect(() => {
+    loadModels();
+  }, []);
+
+  React.useEffect(() => {
+    loadVersions();
+  }, [historyId]);
+
+  const loadModels = async () => {
+    try {
+      const data = await invoke<ModelInfo[]>("get_available_models");
+      const downloaded = data.filter((m) => m.is_downloaded);
+      setModels(downloaded);
+      setModelId((current) => current ?? downloaded[0]?.id ?? null);
+    } catch (error) {
+      console.error("Failed to load models:", error);
+    }
+  };
+
+  const loadVersions = async () => {
+    try {
+      const data = await invoke<VersionComparison[]>("get_transcription_versions", { historyId });
+      setVersions(data);
+    } catch (error) {
+      console.error("Failed to load transcription versions:", error);
+      setVersions([]);
+    }
+  };
+
+  const handleRetranscribe = async () => {
+    if (!modelId) return;
+    setIsRunning(true);
+    try {
+      const comparison = await invoke<VersionComparison>("retranscribe_history_entry", {
+        historyId,
+        modelId,
+        language,
+        translate,
+      });
+      setVersions([comparison, ...versions]);
+    } catch (error) {
+      toast.error(String(error), { description: "Re-transcription Failed" });
+    } finally {
+      setIsRunning(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await invoke("delete_transcription_version", { id });
+      setVersions(versions.filter((v) => v.version.id !== id));
+    } catch (error) {
+      console.error("Failed to delete transcription version:", error);
+    }
+  };
+
+  const modelName = (id: string) => models.find((m) => m.id === id)?.name ?? id;
+
+  const languageName = (code: string | null) =>
+    LANGUAGES.find((l) => l.value === code)?.label ?? code ?? "Default language";
+
+  return (
+    <div>
+      <h3 className="text-sm font-semibold text-foreground uppercase tracking-wide mb-3">
+        Compare Models
+      </h3>
+      <div className="flex flex-wrap items-center gap-2">
+        <Dropdown
+          options={models.map((m) => ({ value: m.id, label: m.name }))}
+          selectedValue={modelId}
+          onSelect={setModelId}
+          placeholder="No downloaded models"
+          disabled={isRunning || models.length === 0}
+          className="min-w-[180px]"
+        />
+        <Dropdown
+          options={LANGUAGES}
+          selectedValue={language}
+          onSelect={setLanguage}
+          disabled={isRunning}
+          className="min-w-[140px]"
+        />
+        <label className="flex items-center gap-2 text-sm text-foreground">
+          <input
+            type="checkbox"
+            checked={translate}
+            onChange={(e) => setTranslate(e.target.checked)}
+            disabled={isRunning}
+          />
+          Translate to English
+        </label>
+        <Button
+          variant="secondary"
+          size="sm"
+          onClick={handleRetranscribe}
+          disabled={isRunning || !modelId}
+          className="flex items-center gap-2"
+        >
+          <RefreshCw className={cn("w-4 h-4", isRunning && "animate-spin")} />
+          {isRunning ? "Transcribing..." : "Re-transcribe"}
+        </Button>
+      </div>
+
+      {versions.map(({ version, diff }) => (
+        <div key={version.id} className="mt-3 bg-muted/50 p-4 rounded-md border border-border">
+          <div className="flex items-center justify-between mb-2 text-xs text-muted-foreground">
+            <span>
+              {modelName(version.model_id)} · {languageName(version.language)}
+              {version.translate && " · translated"}
+            </span>
+            <button
+              onClick={() => handleDelete(version.id)}
+              className="p-1 rounded hover:bg-muted transition-colors text-destructive"
+              title="Delete version"
+            >
+              <Trash2 className="w-4 h-4" />
+            </button>
+          </div>
+          {/* Words only in this version are green, words only in the original are struck through */}
+          <p className="text-foreground whitespace-pre-wrap leading-relaxed">
+            {diff.map((chunk, index) => (
+              <span
+                key={index}
+                className={cn(
+                  chunk.op === "insert" && "bg-green-500/20 text-green-700 dark:text-green-400",
+                  chunk.op === "delete" && "bg-red-500/20 text-red-700 dark:text-red-400 line-through"
+                )}
+              >
+                {chunk.text}{" "}
+              </span>
+            ))}
+          </p>
+        </div>
+      ))}
+    </div>
+  );
+};
@@ -40,12 +40,25 @@ export type PasteMethod = z.infer<typeof PasteMethodSchema>;
 export const ClipboardHandlingSchema = z.enum(["dont_modify", "copy_to_clipboard"]);
 export type ClipboardHandling = z.infer<typeof ClipboardHandlingSchema>;
 
-export const OutputModeSchema = z.enum(["transcript", "ghostwriter"]);
+export const OutputModeSchema = z.enum(["transcript", "ghostwriter", "reply"]);
 export type OutputMode = z.infer<typeof OutputModeSchema>;
 
 export const ThemeModeSchema = z.enum(["system", "light", "dark"]);
 export type ThemeMode = z.infer<typeof ThemeModeSchema>;
 
+// Whisper decoding options; null means "not set here"
+export const DecodingParamsSchema = z.object({
+  beam_size: z.number().nullable().optional(),
+  best_of: z.number().nullable().optional(),
+  temperature: z.number().nullable().optional(),
+  temperature_increment: z.number().nullable().optional(),
+  no_speech_threshold: z.number().nullable().optional(),
+  language: z.string().nullable().optional(),
+  translate: z.boolean().nullable().optional(),
+  threads: z.number().nullable().optional(),
+});
+export type DecodingParams = z.infer<typeof DecodingParamsSchema>;
+
 export const SettingsSchema = z.object({
   bindings: ShortcutBindingsMapSchema,
   push_to_talk: z.boolean(),
@@ -58,11 +71,15 @@ export const SettingsSchema = z.object({
   start_hidden: z.boolean().optional().default(false),
   autostart_enabled: z.boolean().optional().default(false),
   selected_model: z.string(),
+  streaming_model: z.string().nullable().optional(),
+  accurate_final_pass: z.boolean().optional().default(false),
+  long_form_dictation: z.boolean().optional().default(false),
   always_on_microphone: z.boolean(),
   selected_microphone: z.string().nullable().optional(),
   selected_output_device: z.string().nullable().optional(),
   translate_to_english: z.boolean(),
   selected_language: z.string(),
+  allowed_languages: z.array(z.string()).optional().default([]),
   overlay_position: OverlayPositionSchema,
   debug_mode: z.boolean(),
   custom_words: z.array(z.string()).optional().default([]),
@@ -75,11 +92,25 @@ export const SettingsSchema = z.object({
   output_mode: OutputModeSchema.optional().default("transcript"),
   openrouter_api_key: z.string().nullable().optional(),
   openrouter_model: z.string().optional().default("anthropic/claude-3.5-sonnet"),
+  openrouter_fallback_models: z.array(z.string()).optional().default([]),
+  ghostwriter_provider: z.enum(["openrouter", "openai_compatible"]).optional().default("openrouter"),
+  openai_compatible_base_url: z.string().optional().default("http://localhost:11434/v1"),
+  openai_compatible_api_key: z.string().nullable().optional(),
+  openai_compatible_model: z.string().optional().default("llama3.2"),
+  openai_compatible_fallback_models: z.array(z.string()).optional().default([]),
+  ghostwriter_streaming: z.boolean().optional().default(true),
+  ghostwriter_max_retries: z.number().optional().default(2),
+  ghostwriter_guardrails: z.boolean().optional().default(true),
+  low_confidence_threshold: z.number().optional().default(0.5),
+  ghostwriter_verify_hints: z.boolean().optional().default(false),
+  ghostwriter_monthly_budget: z.number().nullable().optional().default(null),
+  decoding_params: DecodingParamsSchema.optional().default({}),
   custom_instructions: z
     .string()
     .max(10000)
     .optional()
     .default("Improve grammar, spelling, clarity, and flow while preserving the original meaning and tone."),
+  user_name: z.string().optional().default(""),
   active_profile_id: z.number().nullable().optional(),
   theme_mode: ThemeModeSchema.optional().default("system"),
 });
@@ -18,6 +18,16 @@ interface ShowOverlayPayload {
   position: OverlayPositionType;
 }
 
+interface StreamingTextEvent {
+  committed: string;
+  tentative: string;
+}
+
+interface FinalTextEvent {
+  text: string;
+  uncertain_words: string[];
+}
+
 const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
@@ -28,6 +38,10 @@ const RecordingOverlay: React.FC = () => {
 
   // Streaming transcription state
   const [words, setWords] = useState<string[]>([]);
+  // Lowercased words from td-final to underline as possibly misheard
+  const [uncertainWords, setUncertainWords] = useState<Set<string>>(new Set());
+  // Trailing words of a streaming result that may still change
+  const [tentativeCount, setTentativeCount] = useState(0);
   const [isStreaming, setIsStreaming] = useState(false);
   const scrollRef = useRef<HTMLDivElement>(null);
   const [shouldAutoScroll, setShouldAutoScroll] = useState(true);
@@ -76,11 +90,22 @@ const RecordingOverlay: React.FC = () => {
         }, 10000);
       });
 
+      // The rewrite came back but failed the guardrails; not a provider problem
+      const unlistenRejected = await listen<string>("ghostwriter-rejected", (event) => {
+        setErrorMessage(`Rewrite rejected: ${event.payload}`);
+        setState("error");
+        setTimeout(() => {
+          setIsVisible(false);
+          setErrorMessage("");
+        }, 10000);
+      });
+
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
         unlistenError();
+        unlistenRejected();
       };
     };
 
@@ -94,6 +119,8 @@ const RecordingOverlay: React.FC = () => {
         console.log("[RecordingOverlay] td-show");
         setIsStreaming(true);
         setWords([]);
+        setUncertainWords(new Set());
+        setTentativeCount(0);
         setShouldAutoScroll(true);
       });
 
@@ -101,21 +128,35 @@ const RecordingOverlay: React.FC = () => {
         console.log("[RecordingOverlay] td-hide");
         setIsStreaming(false);
         setWords([]);
+        setUncertainWords(new Set());
+        setTentativeCount(0);
         setShouldAutoScroll(true);
       });
 
-      const unlistenPartial = await listen<string>("td-partial", (event) => {
-        const text = event.payload as string;
-        console.log("[RecordingOverlay] td-partial", text);
-        const newWords = text.split(/\s+/).filter((w: string) => w.length > 0);
-        setWords(newWords);
+      const unlistenPartial = await listen<StreamingTextEvent>("td-partial", (event) => {
+        const { committed, tentative } = event.payload;
+        console.log("[RecordingOverlay] td-partial", committed, "|", tentative);
+        const committedWords = committed.split(/\s+/).filter((w: string) => w.length > 0);
+        const tentativeWords = tentative.split(/\s+/).filter((w: string) => w.length > 0);
+        setWords([...committedWords, ...tentativeWords]);
+        setTentativeCount(tentativeWords.length);
       });
 
-      const unlistenFinal = await listen<string>("td-final", (event) => {
+      // Ghostwriter output streamed token-by-token (accumulated text so far)
+      const unlistenGhostwriterPartial = await listen<string>("gw-partial", (event) => {
         const text = event.payload as string;
+        setIsStreaming(true);
+        setWords(text.split(/\s+/).filter((w: string) => w.length > 0));
+        setTentativeCount(0);
+      });
+
+      const unlistenFinal = await listen<FinalTextEvent>("td-final", (event) => {
+        const { text, uncertain_words } = event.payload;
         console.log("[RecordingOverlay] td-final");
         const finalWords = text.split(/\s+/).filter((w: string) => w.length > 0);
         setWords(finalWords);
+        setTentativeCount(0);
+        setUncertainWords(new Set(uncertain_words.map((w) => w.toLowerCase())));
         setIsStreaming(false);
       });
 
@@ -123,6 +164,7 @@ const RecordingOverlay: React.FC = () => {
         unlistenShow();
         unlistenHide();
         unlistenPartial();
+        unlistenGhostwriterPartial();
         unlistenFinal();
       };
     };
@@ -161,6 +203,36 @@ const RecordingOverlay: React.FC = () => {
 
   const hasWords = words.length > 0;
 
+  const renderWords = () => {
+    if (tentativeCount > 0) {
+      const stableWords = words.slice(0, words.length - tentativeCount);
+      return (
+        <>
+          {stableWords.join(" ")}
+          {stableWords.length > 0 && " "}
+          <span className="transcription-word-tentative">
+            {words.slice(stableWords.length).join(" ")}
+          </span>
+        </>
+      );
+    }
+    return uncertainWords.size === 0
+      ? words.join(" ")
+      : words.map((word, index) => {
+          const bare = word.replace(/^[^\p{L}\p{N}]+|[^\p{L}\p{N}]+$/gu, "").toLowerCase();
+          return (
+            <React.Fragment key={index}>
+              {index > 0 && " "}
+              {uncertainWords.has(bare) ? (
+                <span className="transcription-word-uncertain">{word}</span>
+              ) : (
+                word
+              )}
+            </React.Fragment>
+          );
+        });
+  };
+
   const getIcon = () => {
     switch (state) {
       case "recording":
@@ -217,7 +289,7 @@ const RecordingOverlay: React.FC = () => {
                     transition={{ duration: 0.2 }}
                   >
                     <span className="transcription-word">
-                      {words.join(" ")}
+                      {renderWords()}
                     </span>
                   </motion.div>
                 )}
@@ -317,7 +389,7 @@ const RecordingOverlay: React.FC = () => {
                     transition={{ duration: 0.2 }}
                   >
                     <span className="transcription-word">
-                      {words.join(" ")}
+                      {renderWords()}
                     </span>
                   </motion.div>
                 )}
@@ -89,8 +89,16 @@ const settingUpdaters: {
     }),
   translate_to_english: (value) =>
     invoke("change_translate_to_english_setting", { enabled: value }),
+  streaming_model: (value) =>
+    invoke("set_streaming_model", { modelId: value ?? null }),
+  accurate_final_pass: (value) =>
+    invoke("change_accurate_final_pass_setting", { enabled: value }),
+  long_form_dictation: (value) =>
+    invoke("change_long_form_dictation_setting", { enabled: value }),
   selected_language: (value) =>
     invoke("change_selected_language_setting", { language: value }),
+  allowed_languages: (value) =>
+    invoke("change_allowed_languages_setting", { languages: value }),
   overlay_position: (value) =>
     invoke("change_overlay_position_setting", { position: value }),
   debug_mode: (value) =>
@@ -111,8 +119,36 @@ const settingUpdaters: {
     invoke("change_openrouter_api_key_setting", { apiKey: value }),
   openrouter_model: (value) =>
     invoke("change_openrouter_model_setting", { model: value }),
+  openrouter_fallback_models: (value) =>
+    invoke("change_openrouter_fallback_models_setting", { models: value }),
   custom_instructions: (value) =>
     invoke("change_custom_instructions_setting", { instructions: value }),
+  ghostwriter_monthly_budget: (value) =>
+    invoke("change_ghostwriter_monthly_budget_setting", { budget: value ?? null }),
+  decoding_params: (value) =>
+    invoke("change_decoding_params_setting", { params: value }),
+  user_name: (value) =>
+    invoke("change_user_name_setting", { userName: value }),
+  ghostwriter_provider: (value) =>
+    invoke("change_ghostwriter_provider_setting", { provider: value }),
+  ghostwriter_streaming: (value) =>
+    invoke("change_ghostwriter_streaming_setting", { enabled: value }),
+  ghostwriter_max_retries: (value) =>
+    invoke("change_ghostwriter_max_retries_setting", { retries: value }),
+  ghostwriter_guardrails: (value) =>
+    invoke("change_ghostwriter_guardrails_setting", { enabled: value }),
+  low_confidence_threshold: (value) =>
+    invoke("change_low_confidence_threshold_setting", { threshold: value }),
+  ghostwriter_verify_hints: (value) =>
+    invoke("change_ghostwriter_verify_hints_setting", { enabled: value }),
+  openai_compatible_base_url: (value) =>
+    invoke("change_openai_compatible_base_url_setting", { baseUrl: value }),
+  openai_compatible_api_key: (value) =>
+    invoke("change_openai_compatible_api_key_setting", { apiKey: value }),
+  openai_compatible_model: (value) =>
+    invoke("change_openai_compatible_model_setting", { model: value }),
+  openai_compatible_fallback_models: (value) =>
+    invoke("change_openai_compatible_fallback_models_setting", { models: value }),
   active_profile_id: async (value) => {
     // Save via backend to avoid race conditions with store
     const { load } = await import("@tauri-apps/plugin-store");